@@ -7,6 +7,7 @@ func _ready() -> void:
 	test_circle_shape()
 	test_concave_polygon_shape()
 	test_convex_polygon_shape()
+	test_convex_decomposition_shape()
 	print("Success")
 	await get_tree().create_timer(1.0).timeout
 	get_tree().quit()
@@ -28,3 +29,7 @@ func test_concave_polygon_shape():
 func test_convex_polygon_shape():
 	RapierConvexPolygonShapeTests.test_create()
 	RapierConvexPolygonShapeTests.test_set_data()
+
+func test_convex_decomposition_shape():
+	RapierConvexDecompositionShapeTests.test_create()
+	RapierConvexDecompositionShapeTests.test_set_data()
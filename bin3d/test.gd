@@ -7,7 +7,9 @@ func _ready() -> void:
 	test_circle_shape()
 	test_concave_polygon_shape()
 	test_convex_polygon_shape()
+	test_convex_decomposition_shape()
 	test_cylinder_shape()
+	test_box_shape()
 	print("Success")
 	await get_tree().create_timer(1.0).timeout
 	get_tree().quit()
@@ -35,3 +37,10 @@ func test_cylinder_shape():
 	RapierCylinderShape3DTests.test_set_data_array()
 	RapierCylinderShape3DTests.test_set_data_vector2()
 	RapierCylinderShape3DTests.test_set_data_dictionary()
+
+func test_convex_decomposition_shape():
+	RapierConvexDecompositionShapeTests.test_create()
+	RapierConvexDecompositionShapeTests.test_set_data()
+
+func test_box_shape():
+	RapierBoxShape3DTests.test_skewed_hull()
@@ -1,4 +1,5 @@
 use bodies::rapier_collision_object_base::CollisionObjectShape;
+use bodies::rapier_collision_object_base::CollisionObjectShapeMaterial;
 use bodies::rapier_collision_object_base::CollisionObjectType;
 use bodies::rapier_collision_object_base::RapierCollisionObjectBase;
 use bodies::rapier_collision_object_base::RapierCollisionObjectBaseState;
@@ -9,6 +10,9 @@ use godot::classes::physics_server_3d::*;
 use godot::prelude::*;
 use hashbrown::hash_set::HashSet;
 #[cfg(feature = "dim3")]
+use rapier::control::DynamicRayCastVehicleController;
+use rapier::dynamics::CoefficientCombineRule;
+#[cfg(feature = "dim3")]
 use rapier::dynamics::LockedAxes;
 use rapier::geometry::ColliderHandle;
 #[cfg(feature = "dim3")]
@@ -19,6 +23,7 @@ use servers::rapier_physics_singleton::PhysicsIds;
 use servers::rapier_physics_singleton::PhysicsShapes;
 use servers::rapier_physics_singleton::PhysicsSpaces;
 use servers::rapier_physics_singleton::RapierId;
+use servers::rapier_project_settings::RapierProjectSettings;
 use shapes::rapier_shape::IRapierShape;
 
 use super::rapier_area::RapierArea;
@@ -45,6 +50,27 @@ pub struct Contact {
     pub local_velocity_at_pos: Vector,
     pub collider_velocity_at_pos: Vector,
     pub impulse: Vector,
+    pub tangent_impulse: real,
+    pub local_feature_id: u32,
+    pub collider_feature_id: u32,
+    pub friction: real,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyCcdMode {
+    Disabled,
+    // Cheap CCD that enlarges the contact prediction by the motion of the body.
+    Soft,
+    // Time of impact based CCD.
+    Hard,
+}
+impl BodyCcdMode {
+    pub fn from_i32(value: i32) -> BodyCcdMode {
+        match value {
+            1 => BodyCcdMode::Soft,
+            2 => BodyCcdMode::Hard,
+            _ => BodyCcdMode::Disabled,
+        }
+    }
 }
 pub struct AreaOverrideSettings {
     using_area_gravity: bool,
@@ -71,6 +97,10 @@ impl Default for Contact {
             local_velocity_at_pos: Vector::default(),
             collider_velocity_at_pos: Vector::default(),
             impulse: Vector::default(),
+            tangent_impulse: 0.0,
+            local_feature_id: 0,
+            collider_feature_id: 0,
+            friction: 0.0,
         }
     }
 }
@@ -136,6 +166,17 @@ pub struct RapierBodyState {
     pub(crate) areas: Vec<IdWithPriority>,
     pub(crate) contacts: Vec<Contact>,
     pub(crate) contact_count: i32,
+    pub(crate) sleep_linear_threshold: Option<real>,
+    pub(crate) sleep_angular_threshold: Option<real>,
+    pub(crate) time_before_sleep: Option<real>,
+    pub(crate) dominance_group: i8,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) mass_from_density: bool,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) additional_mass: real,
+    // Mass computed from the shape densities, the explicit mass is kept as is.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) density_mass: real,
 }
 #[derive(Debug)]
 pub struct RapierBody {
@@ -149,13 +190,20 @@ pub struct RapierBody {
     #[cfg(feature = "dim3")]
     axis_lock: u8,
     contact_skin: real,
+    friction_combine_rule: CoefficientCombineRule,
+    restitution_combine_rule: CoefficientCombineRule,
     calculate_inertia: bool,
     calculate_center_of_mass: bool,
     using_area_gravity: bool,
     using_area_linear_damping: bool,
     using_area_angular_damping: bool,
     exceptions: HashSet<Rid>,
-    ccd_enabled: bool,
+    collision_priority: real,
+    contacts_reported_depth_threshold: real,
+    ccd_mode: BodyCcdMode,
+    soft_ccd_prediction: Option<real>,
+    #[cfg(feature = "dim3")]
+    vehicle: Option<DynamicRayCastVehicleController>,
     omit_force_integration: bool,
     can_sleep: bool,
     sleep: bool,
@@ -183,13 +231,20 @@ impl RapierBody {
             #[cfg(feature = "dim3")]
             axis_lock: 0,
             contact_skin: 0.0,
+            friction_combine_rule: CoefficientCombineRule::Min,
+            restitution_combine_rule: CoefficientCombineRule::Sum,
             calculate_inertia: true,
             calculate_center_of_mass: true,
             using_area_gravity: false,
             using_area_linear_damping: false,
             using_area_angular_damping: false,
             exceptions: HashSet::default(),
-            ccd_enabled: false,
+            collision_priority: 1.0,
+            contacts_reported_depth_threshold: 0.0,
+            ccd_mode: BodyCcdMode::Disabled,
+            soft_ccd_prediction: None,
+            #[cfg(feature = "dim3")]
+            vehicle: None,
             omit_force_integration: false,
             can_sleep: true,
             sleep: false,
@@ -210,7 +265,7 @@ impl RapierBody {
         if self.base.mode.ord() < BodyMode::RIGID.ord() {
             return;
         }
-        if self.calculate_inertia || self.calculate_center_of_mass {
+        if self.calculate_inertia || self.calculate_center_of_mass || self.state.mass_from_density {
             if let Some(space) = physics_spaces.get_mut(&self.base.get_space(physics_ids)) {
                 space
                     .get_mut_state()
@@ -235,7 +290,7 @@ impl RapierBody {
         physics_engine.body_set_mass_properties(
             self.base.get_space_id(),
             self.base.get_body_handle(),
-            self.state.mass,
+            self.get_mass(),
             angle_to_rapier(inertia_value),
             vector_to_rapier(self.state.center_of_mass),
             false,
@@ -360,9 +415,10 @@ impl RapierBody {
             filter_contacts_enabled,
         );
         // if we are a conveyer belt, we need to modify contacts
-        // also if any shape is one-way
+        // also if any shape is one-way or the collision priority is not the default one
         let modify_contacts_enabled = self.base.mode == BodyMode::STATIC
             || self.base.mode == BodyMode::KINEMATIC
+            || self.collision_priority != 1.0
             || override_modify_contacts;
         physics_engine.collider_set_modify_contacts_enabled(
             space_handle,
@@ -828,7 +884,7 @@ impl RapierBody {
         if !self.using_area_gravity || !self.base.is_valid() {
             return;
         }
-        let gravity_impulse = self.state.total_gravity * self.state.mass * p_step;
+        let gravity_impulse = self.state.total_gravity * self.get_mass() * p_step;
         physics_engine.body_apply_impulse(
             self.base.get_space_id(),
             self.base.get_body_handle(),
@@ -863,6 +919,14 @@ impl RapierBody {
         !self.state.contacts.is_empty()
     }
 
+    pub fn set_contacts_reported_depth_threshold(&mut self, threshold: real) {
+        self.contacts_reported_depth_threshold = threshold;
+    }
+
+    pub fn get_contacts_reported_depth_threshold(&self) -> real {
+        self.contacts_reported_depth_threshold
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn add_contact(
         &mut self,
@@ -877,9 +941,13 @@ impl RapierBody {
         collider: RapierId,
         collider_velocity_at_pos: Vector,
         impulse: Vector,
+        tangent_impulse: real,
+        local_feature_id: u32,
+        collider_feature_id: u32,
+        friction: real,
     ) {
         let c_max = self.state.contacts.len();
-        if c_max == 0 {
+        if c_max == 0 || depth < self.contacts_reported_depth_threshold {
             return;
         }
         let mut idx = -1;
@@ -914,6 +982,10 @@ impl RapierBody {
         c.collider_velocity_at_pos = collider_velocity_at_pos;
         c.local_velocity_at_pos = local_velocity_at_pos;
         c.impulse = impulse;
+        c.tangent_impulse = tangent_impulse;
+        c.local_feature_id = local_feature_id;
+        c.collider_feature_id = collider_feature_id;
+        c.friction = friction;
     }
 
     pub fn add_exception(&mut self, exception: Rid, physics_engine: &mut PhysicsEngine) {
@@ -934,6 +1006,86 @@ impl RapierBody {
         &self.exceptions
     }
 
+    // Used by motion recovery, and by the contact modification to scale the penetration recovery between two rigid bodies.
+    pub fn set_collision_priority(&mut self, priority: real, physics_engine: &mut PhysicsEngine) {
+        if priority <= 0.0 {
+            godot_error!("Collision priority must be greater than 0.");
+            return;
+        }
+        self.collision_priority = priority;
+        self.update_colliders_filters(physics_engine);
+    }
+
+    pub fn get_collision_priority(&self) -> real {
+        self.collision_priority
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn set_vehicle_enabled(
+        &mut self,
+        enabled: bool,
+        physics_spaces: &mut PhysicsSpaces,
+        physics_ids: &PhysicsIds,
+    ) {
+        if enabled == self.vehicle.is_some() {
+            return;
+        }
+        if enabled {
+            let mut vehicle = DynamicRayCastVehicleController::new(self.base.get_body_handle());
+            // Same as VehicleBody3D, the vehicle drives along its local Z axis.
+            vehicle.index_forward_axis = 2;
+            self.vehicle = Some(vehicle);
+        } else {
+            self.vehicle = None;
+        }
+        self.update_vehicle_update_list(physics_spaces, physics_ids);
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn get_vehicle(&self) -> Option<&DynamicRayCastVehicleController> {
+        self.vehicle.as_ref()
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn get_mut_vehicle(&mut self) -> Option<&mut DynamicRayCastVehicleController> {
+        self.vehicle.as_mut()
+    }
+
+    // The vehicle is moved out of the body while it is stepped, as its raycasts need to read the other collision objects.
+    #[cfg(feature = "dim3")]
+    pub fn take_vehicle(&mut self) -> Option<DynamicRayCastVehicleController> {
+        let mut vehicle = self.vehicle.take();
+        if let Some(vehicle) = &mut vehicle {
+            // the body handle changes when the body is recreated
+            vehicle.chassis = self.base.get_body_handle();
+        }
+        vehicle
+    }
+
+    #[cfg(feature = "dim3")]
+    pub fn restore_vehicle(&mut self, vehicle: DynamicRayCastVehicleController) {
+        self.vehicle = Some(vehicle);
+    }
+
+    #[cfg(feature = "dim3")]
+    fn update_vehicle_update_list(
+        &self,
+        physics_spaces: &mut PhysicsSpaces,
+        physics_ids: &PhysicsIds,
+    ) {
+        if let Some(space) = physics_spaces.get_mut(&self.base.get_space(physics_ids)) {
+            if self.vehicle.is_some() && self.base.mode.ord() >= BodyMode::RIGID.ord() {
+                space
+                    .get_mut_state()
+                    .body_add_to_vehicle_update_list(self.base.get_id());
+            } else {
+                space
+                    .get_mut_state()
+                    .body_remove_from_vehicle_update_list(self.base.get_id());
+            }
+        }
+    }
+
     pub fn set_omit_force_integration(&mut self, omit_force_integration: bool) {
         self.omit_force_integration = omit_force_integration;
     }
@@ -1181,16 +1333,48 @@ impl RapierBody {
         if !self.base.is_valid() {
             return;
         }
+        let (angular_threshold, linear_threshold, time_until_sleep) = self.get_activation();
         physics_engine.body_set_can_sleep(
             self.base.get_space_id(),
             self.base.get_body_handle(),
             p_can_sleep,
-            self.base.activation_angular_threshold,
-            self.base.activation_linear_threshold,
-            self.base.activation_time_until_sleep,
+            angular_threshold,
+            linear_threshold,
+            time_until_sleep,
         );
     }
 
+    // The body overrides take precedence over the values of the space.
+    fn get_activation(&self) -> (real, real, real) {
+        let angular_threshold = self
+            .state
+            .sleep_angular_threshold
+            .unwrap_or(self.base.activation_angular_threshold);
+        let linear_threshold = self
+            .state
+            .sleep_linear_threshold
+            .map(|threshold| threshold / RapierProjectSettings::get_length_unit())
+            .unwrap_or(self.base.activation_linear_threshold);
+        let time_until_sleep = self
+            .state
+            .time_before_sleep
+            .unwrap_or(self.base.activation_time_until_sleep);
+        (angular_threshold, linear_threshold, time_until_sleep)
+    }
+
+    pub fn set_sleep_thresholds(
+        &mut self,
+        angular_threshold: real,
+        linear_threshold: real,
+        time_until_sleep: real,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.base.activation_angular_threshold = angular_threshold;
+        self.base.activation_linear_threshold = linear_threshold;
+        self.base.activation_time_until_sleep = time_until_sleep;
+        self.set_can_sleep(self.can_sleep, physics_engine);
+    }
+
     pub fn on_marked_active(&mut self, space: &mut RapierSpace) {
         if self.base.mode == BodyMode::STATIC {
             return;
@@ -1274,15 +1458,7 @@ impl RapierBody {
                 } else {
                     self.friction = variant_to_float(&p_value);
                 }
-                if !self.base.is_valid() {
-                    return;
-                }
-                let mat = self.init_material();
-                physics_engine.body_update_material(
-                    self.base.get_space_id(),
-                    self.base.get_body_handle(),
-                    &mat,
-                );
+                self.update_material(physics_engine);
             }
             BodyParameter::MASS => {
                 if p_value.get_type() != VariantType::FLOAT
@@ -1295,11 +1471,7 @@ impl RapierBody {
                     return;
                 }
                 self.state.mass = mass_value;
-                if self.state.mass.is_zero_approx() {
-                    self.state.inv_mass = 0.0;
-                } else {
-                    self.state.inv_mass = 1.0 / self.state.mass;
-                }
+                self.update_inv_mass();
                 if self.base.mode.ord() >= BodyMode::RIGID.ord() {
                     self.mass_properties_changed(physics_engine, physics_spaces, physics_ids);
                 }
@@ -1480,6 +1652,8 @@ impl RapierBody {
         p_param: RapierBodyParam,
         p_value: Variant,
         physics_engine: &mut PhysicsEngine,
+        physics_spaces: &mut PhysicsSpaces,
+        physics_ids: &PhysicsIds,
     ) {
         match p_param {
             RapierBodyParam::ContactSkin => {
@@ -1489,19 +1663,112 @@ impl RapierBody {
                     return;
                 }
                 self.contact_skin = variant_to_float(&p_value);
-                let mat = self.init_material();
-                let body_handle = self.base.get_body_handle();
-                let space_handle = self.base.get_space_id();
-                if self.base.is_valid() {
-                    physics_engine.body_update_material(space_handle, body_handle, &mat);
+            }
+            RapierBodyParam::FrictionCombineRule => {
+                if p_value.get_type() != VariantType::INT {
+                    return;
+                }
+                let Some(rule) = combine_rule_from_i32(p_value.to()) else {
+                    return;
+                };
+                self.friction_combine_rule = rule;
+            }
+            RapierBodyParam::RestitutionCombineRule => {
+                if p_value.get_type() != VariantType::INT {
+                    return;
+                }
+                let Some(rule) = combine_rule_from_i32(p_value.to()) else {
+                    return;
+                };
+                self.restitution_combine_rule = rule;
+            }
+            RapierBodyParam::SleepLinearThreshold => {
+                self.state.sleep_linear_threshold = variant_to_optional_float(&p_value);
+                self.set_can_sleep(self.can_sleep, physics_engine);
+                return;
+            }
+            RapierBodyParam::SleepAngularThreshold => {
+                self.state.sleep_angular_threshold = variant_to_optional_float(&p_value);
+                self.set_can_sleep(self.can_sleep, physics_engine);
+                return;
+            }
+            RapierBodyParam::TimeBeforeSleep => {
+                self.state.time_before_sleep = variant_to_optional_float(&p_value);
+                self.set_can_sleep(self.can_sleep, physics_engine);
+                return;
+            }
+            RapierBodyParam::CcdMode => {
+                if p_value.get_type() != VariantType::INT {
+                    return;
+                }
+                self.set_continuous_collision_detection_mode(
+                    BodyCcdMode::from_i32(p_value.to()),
+                    physics_engine,
+                );
+                return;
+            }
+            RapierBodyParam::SoftCcdPrediction => {
+                self.soft_ccd_prediction = variant_to_optional_float(&p_value);
+                self.apply_ccd(physics_engine);
+                return;
+            }
+            RapierBodyParam::DominanceGroup => {
+                if p_value.get_type() != VariantType::INT {
+                    return;
+                }
+                let dominance_group = p_value.to::<i32>().clamp(i8::MIN as i32, i8::MAX as i32);
+                self.set_dominance_group(dominance_group as i8, physics_engine);
+                return;
+            }
+            RapierBodyParam::MassFromDensity => {
+                if p_value.get_type() != VariantType::BOOL {
+                    return;
+                }
+                self.state.mass_from_density = p_value.to();
+                self.update_inv_mass();
+                self.mass_properties_changed(physics_engine, physics_spaces, physics_ids);
+                return;
+            }
+            RapierBodyParam::AdditionalMass => {
+                if p_value.get_type() != VariantType::FLOAT
+                    && p_value.get_type() != VariantType::INT
+                {
+                    return;
                 }
+                self.state.additional_mass = variant_to_float(&p_value).max(0.0);
+                self.update_inv_mass();
+                self.mass_properties_changed(physics_engine, physics_spaces, physics_ids);
+                return;
             }
         }
+        self.update_material(physics_engine);
     }
 
     pub fn get_extra_param(&self, p_param: RapierBodyParam) -> Variant {
         match p_param {
             RapierBodyParam::ContactSkin => self.contact_skin.to_variant(),
+            RapierBodyParam::FrictionCombineRule => {
+                combine_rule_to_i32(self.friction_combine_rule).to_variant()
+            }
+            RapierBodyParam::RestitutionCombineRule => {
+                combine_rule_to_i32(self.restitution_combine_rule).to_variant()
+            }
+            RapierBodyParam::SleepLinearThreshold => {
+                optional_float_to_variant(self.state.sleep_linear_threshold)
+            }
+            RapierBodyParam::SleepAngularThreshold => {
+                optional_float_to_variant(self.state.sleep_angular_threshold)
+            }
+            RapierBodyParam::TimeBeforeSleep => {
+                optional_float_to_variant(self.state.time_before_sleep)
+            }
+            RapierBodyParam::CcdMode => (self.ccd_mode as i32).to_variant(),
+            RapierBodyParam::SoftCcdPrediction => {
+                optional_float_to_variant(self.soft_ccd_prediction)
+            }
+            RapierBodyParam::DominanceGroup => (self.state.dominance_group as i32).to_variant(),
+            RapierBodyParam::MassFromDensity => self.state.mass_from_density.to_variant(),
+            RapierBodyParam::AdditionalMass => self.state.additional_mass.to_variant(),
         }
     }
 
@@ -1525,6 +1792,7 @@ impl RapierBody {
                         space.get_state().get_id(),
                         self.base.get_body_handle(),
                         BodyType::Kinematic,
+                        self.state.dominance_group,
                         true,
                     );
                 }
@@ -1533,6 +1801,7 @@ impl RapierBody {
                         space.get_state().get_id(),
                         self.base.get_body_handle(),
                         BodyType::Static,
+                        self.state.dominance_group,
                         true,
                     );
                 }
@@ -1541,6 +1810,7 @@ impl RapierBody {
                         space.get_state().get_id(),
                         self.base.get_body_handle(),
                         BodyType::Dynamic,
+                        self.state.dominance_group,
                         true,
                     );
                 }
@@ -1558,6 +1828,10 @@ impl RapierBody {
                 space
                     .get_mut_state()
                     .body_remove_from_gravity_update_list(id);
+                #[cfg(feature = "dim3")]
+                space
+                    .get_mut_state()
+                    .body_remove_from_vehicle_update_list(id);
                 space.get_mut_state().body_remove_from_area_update_list(id);
                 return;
             }
@@ -1690,22 +1964,47 @@ impl RapierBody {
 
     pub fn set_continuous_collision_detection_mode(
         &mut self,
-        enabled: bool,
+        mode: BodyCcdMode,
         physics_engine: &mut PhysicsEngine,
     ) {
-        self.ccd_enabled = enabled;
+        self.ccd_mode = mode;
+        self.apply_ccd(physics_engine);
+    }
+
+    pub fn get_continuous_collision_detection_mode(&self) -> BodyCcdMode {
+        self.ccd_mode
+    }
+
+    pub fn set_dominance_group(&mut self, dominance_group: i8, physics_engine: &mut PhysicsEngine) {
+        self.state.dominance_group = dominance_group;
+        self.base.dominance_group = dominance_group;
         if !self.base.is_valid() {
             return;
         }
-        physics_engine.body_set_ccd_enabled(
+        physics_engine.body_set_dominance_group(
             self.base.get_space_id(),
             self.base.get_body_handle(),
-            self.ccd_enabled,
+            dominance_group,
         );
     }
 
-    pub fn get_continuous_collision_detection_mode(&self) -> bool {
-        self.ccd_enabled
+    fn apply_ccd(&self, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() {
+            return;
+        }
+        let mut soft_ccd_prediction = 0.0;
+        if self.ccd_mode == BodyCcdMode::Soft {
+            // defaults to one meter
+            soft_ccd_prediction = self
+                .soft_ccd_prediction
+                .unwrap_or(RapierProjectSettings::get_length_unit());
+        }
+        physics_engine.body_set_ccd_enabled(
+            self.base.get_space_id(),
+            self.base.get_body_handle(),
+            self.ccd_mode == BodyCcdMode::Hard,
+            soft_ccd_prediction,
+        );
     }
 
     pub fn update_mass_properties(
@@ -1718,19 +2017,39 @@ impl RapierBody {
             return;
         }
         // compute rigidbody mass properties by changing collider mass. Will get overriden later
-        let rigid_body_mass_properties = physics_engine
-            .body_get_mass_properties(self.base.get_space_id(), self.base.get_body_handle());
-        if self.calculate_center_of_mass {
+        let (rigid_body_mass_properties, inertia_scale) = if self.state.mass_from_density {
+            let mut densities = Vec::new();
+            for shape in &self.base.state.shapes {
+                if shape.collider_handle != ColliderHandle::invalid() {
+                    densities.push((shape.collider_handle, shape.density));
+                }
+            }
+            let rigid_body_mass_properties = physics_engine.body_get_density_mass_properties(
+                self.base.get_space_id(),
+                self.base.get_body_handle(),
+                &densities,
+                self.state.additional_mass,
+            );
+            // the additional mass is already part of it
+            self.state.density_mass = rigid_body_mass_properties.local_mprops.mass();
+            self.update_inv_mass();
+            // the inertia already accounts for the mass
+            (rigid_body_mass_properties, 1.0)
+        } else {
+            let (rigid_body_mass_properties, colliders_count) = physics_engine
+                .body_get_mass_properties(self.base.get_space_id(), self.base.get_body_handle());
+            (
+                rigid_body_mass_properties,
+                self.get_mass() / (colliders_count as real),
+            )
+        };
+        if self.calculate_center_of_mass || self.state.mass_from_density {
             self.state.center_of_mass =
-                vector_to_godot(rigid_body_mass_properties.0.local_mprops.local_com.coords);
+                vector_to_godot(rigid_body_mass_properties.local_mprops.local_com.coords);
         }
-        if self.calculate_inertia {
-            let angular_inertia = rigid_body_mass_properties
-                .0
-                .local_mprops
-                .principal_inertia();
-            self.state.inertia = angle_to_godot(angular_inertia) * self.state.mass
-                / (rigid_body_mass_properties.1 as real);
+        if self.calculate_inertia || self.state.mass_from_density {
+            let angular_inertia = rigid_body_mass_properties.local_mprops.principal_inertia();
+            self.state.inertia = angle_to_godot(angular_inertia) * inertia_scale;
         }
         if self.state.inertia.is_zero_approx() {
             self.state.inv_inertia = ANGLE_ZERO;
@@ -1759,7 +2078,6 @@ impl RapierBody {
             }
             // inv inertia tensor
             let rotation_matrix = rigid_body_mass_properties
-                .0
                 .local_mprops
                 .principal_inertia_local_frame
                 .to_rotation_matrix();
@@ -1782,7 +2100,6 @@ impl RapierBody {
                 Vector3::new(column_2.x, column_2.y, column_2.z),
             );
             let inv_inertia = rigid_body_mass_properties
-                .0
                 .local_mprops
                 .inv_principal_inertia_sqrt;
             let tb = self.state.principal_inertia_axes;
@@ -1813,6 +2130,25 @@ impl RapierBody {
         self.state.center_of_mass
     }
 
+    // The additional mass is added on top of the explicit mass.
+    // In density mode it is already part of the density mass.
+    pub fn get_mass(&self) -> real {
+        if self.state.mass_from_density {
+            self.state.density_mass
+        } else {
+            self.state.mass + self.state.additional_mass
+        }
+    }
+
+    fn update_inv_mass(&mut self) {
+        let mass = self.get_mass();
+        if mass.is_zero_approx() {
+            self.state.inv_mass = 0.0;
+        } else {
+            self.state.inv_mass = 1.0 / mass;
+        }
+    }
+
     pub fn get_inv_mass(&self) -> real {
         self.state.inv_mass
     }
@@ -1932,6 +2268,10 @@ impl RapierBody {
             space
                 .get_mut_state()
                 .body_remove_from_gravity_update_list(id);
+            #[cfg(feature = "dim3")]
+            space
+                .get_mut_state()
+                .body_remove_from_vehicle_update_list(id);
             space.get_mut_state().body_remove_from_active_list(id);
             space.get_mut_state().body_remove_from_state_query_list(id);
             space.get_mut_state().body_remove_from_area_update_list(id);
@@ -1959,9 +2299,7 @@ impl RapierBody {
                     space.get_mut_state().body_add_to_state_query_list(id);
                 }
             }
-            if !self.can_sleep {
-                self.set_can_sleep(false, physics_engine);
-            }
+            self.set_can_sleep(self.can_sleep, physics_engine);
             if self.state.active || !self.sleep {
                 self.wakeup(physics_engine);
                 if let Some(space) = physics_spaces.get_mut(&self.base.get_space(physics_ids)) {
@@ -2025,14 +2363,50 @@ impl RapierBody {
                 }
                 #[cfg(feature = "dim3")]
                 self.apply_axis_lock(physics_engine);
-                self.set_continuous_collision_detection_mode(self.ccd_enabled, physics_engine);
-                physics_engine.body_update_material(
-                    self.base.get_space_id(),
-                    self.base.get_body_handle(),
-                    &self.init_material(),
-                );
+                self.apply_ccd(physics_engine);
+                self.update_material(physics_engine);
             }
         }
+        #[cfg(feature = "dim3")]
+        self.update_vehicle_update_list(physics_spaces, physics_ids);
+    }
+
+    fn update_material(&self, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() {
+            return;
+        }
+        let material = self.init_material();
+        physics_engine.body_update_material(
+            self.base.get_space_id(),
+            self.base.get_body_handle(),
+            &material,
+        );
+        // Shapes with their own material or a margin get their collider material on top of it.
+        self.base.update_shapes_material(material, physics_engine);
+    }
+
+    pub fn set_shape_material(
+        &mut self,
+        p_shape_idx: usize,
+        p_material: CollisionObjectShapeMaterial,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.base.set_shape_material(p_shape_idx, p_material);
+        self.update_material(physics_engine);
+    }
+
+    pub fn set_shape_density(
+        &mut self,
+        p_shape_idx: usize,
+        p_density: real,
+        physics_engine: &mut PhysicsEngine,
+        physics_spaces: &mut PhysicsSpaces,
+        physics_ids: &PhysicsIds,
+    ) {
+        self.base.set_shape_density(p_shape_idx, p_density.max(0.0));
+        if self.state.mass_from_density {
+            self.mass_properties_changed(physics_engine, physics_spaces, physics_ids);
+        }
     }
 }
 // We won't use the pointers between threads, so it should be safe.
@@ -2238,6 +2612,8 @@ impl IRapierCollisionObject for RapierBody {
         Material {
             friction: Some(self.friction),
             restitution: Some(self.bounce),
+            friction_combine_rule: Some(self.friction_combine_rule),
+            restitution_combine_rule: Some(self.restitution_combine_rule),
             contact_skin: Some(self.contact_skin),
             collision_layer: Some(self.base.get_collision_layer()),
             collision_mask: Some(self.base.get_collision_mask()),
@@ -2316,6 +2692,7 @@ impl IRapierCollisionObject for RapierBody {
             Ok(import) => {
                 self.state = import.body_state;
                 self.base.state = import.base_state;
+                self.base.dominance_group = self.state.dominance_group;
             }
             Err(e) => {
                 godot_error!("Failed to deserialize body from binary: {}", e);
@@ -2330,3 +2707,29 @@ impl Drop for RapierBody {
         }
     }
 }
+// Combine rules are exposed as 0 Average, 1 Min, 2 Multiply, 3 Max, 4 Sum.
+fn combine_rule_from_i32(value: i32) -> Option<CoefficientCombineRule> {
+    match value {
+        0 => Some(CoefficientCombineRule::Average),
+        1 => Some(CoefficientCombineRule::Min),
+        2 => Some(CoefficientCombineRule::Multiply),
+        3 => Some(CoefficientCombineRule::Max),
+        4 => Some(CoefficientCombineRule::Sum),
+        _ => {
+            godot_error!(
+                "Invalid combine rule {}, expected a value between 0 and 4",
+                value
+            );
+            None
+        }
+    }
+}
+fn combine_rule_to_i32(rule: CoefficientCombineRule) -> i32 {
+    match rule {
+        CoefficientCombineRule::Average => 0,
+        CoefficientCombineRule::Min => 1,
+        CoefficientCombineRule::Multiply => 2,
+        CoefficientCombineRule::Max => 3,
+        CoefficientCombineRule::Sum => 4,
+    }
+}
@@ -2,7 +2,6 @@
 use godot::classes::physics_server_2d::*;
 #[cfg(feature = "dim3")]
 use godot::classes::physics_server_3d::*;
-use godot::classes::ProjectSettings;
 use godot::prelude::*;
 use rapier::dynamics::RigidBodyHandle;
 use rapier::geometry::ColliderHandle;
@@ -16,18 +15,6 @@ use spaces::rapier_space::RapierSpace;
 use crate::rapier_wrapper::prelude::*;
 use crate::types::*;
 use crate::*;
-#[cfg(feature = "dim2")]
-const SLEEP_THRESHOLD_LINEAR: &str = "physics/2d/sleep_threshold_linear";
-#[cfg(feature = "dim3")]
-const SLEEP_THRESHOLD_LINEAR: &str = "physics/3d/sleep_threshold_linear";
-#[cfg(feature = "dim2")]
-const SLEEP_THRESHOLD_ANGULAR: &str = "physics/2d/sleep_threshold_angular";
-#[cfg(feature = "dim3")]
-const SLEEP_THRESHOLD_ANGULAR: &str = "physics/3d/sleep_threshold_angular";
-#[cfg(feature = "dim2")]
-const TIME_BEFORE_SLEEP: &str = "physics/2d/time_before_sleep";
-#[cfg(feature = "dim3")]
-const TIME_BEFORE_SLEEP: &str = "physics/3d/time_before_sleep";
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(
     feature = "serde-serialize",
@@ -49,6 +36,49 @@ pub struct CollisionObjectShape {
     pub one_way_collision: bool,
     pub one_way_collision_margin: real,
     pub collider_handle: ColliderHandle,
+    pub material: CollisionObjectShapeMaterial,
+    pub density: real,
+    // Margin of the shape, added as contact skin of the collider.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub margin: real,
+}
+impl CollisionObjectShape {
+    pub fn collider_material(&self, mat: Material) -> Material {
+        let mut mat = self.material.override_material(mat);
+        if self.margin > 0.0 {
+            mat.contact_skin = Some(mat.contact_skin.unwrap_or_default() + self.margin);
+        }
+        mat
+    }
+}
+// Overrides of the body material for a single shape.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct CollisionObjectShapeMaterial {
+    pub friction: Option<real>,
+    pub bounce: Option<real>,
+    pub contact_skin: Option<real>,
+}
+impl CollisionObjectShapeMaterial {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn override_material(&self, mut mat: Material) -> Material {
+        if let Some(friction) = self.friction {
+            mat.friction = Some(friction);
+        }
+        if let Some(bounce) = self.bounce {
+            mat.restitution = Some(bounce);
+        }
+        if let Some(contact_skin) = self.contact_skin {
+            mat.contact_skin = Some(contact_skin);
+        }
+        mat
+    }
 }
 #[derive(Default, Debug, Clone)]
 #[cfg_attr(
@@ -80,6 +110,7 @@ pub struct RapierCollisionObjectBase {
     pub(crate) activation_angular_threshold: real,
     pub(crate) activation_linear_threshold: real,
     pub(crate) activation_time_until_sleep: real,
+    pub(crate) dominance_group: i8,
 }
 impl Default for RapierCollisionObjectBase {
     fn default() -> Self {
@@ -92,21 +123,6 @@ impl RapierCollisionObjectBase {
         if collision_object_type == CollisionObjectType::Area {
             mode = BodyMode::STATIC;
         }
-        let project_settings = ProjectSettings::singleton();
-        let activation_angular_threshold = project_settings
-            .get_setting_with_override(SLEEP_THRESHOLD_ANGULAR)
-            .try_to()
-            .unwrap_or_default();
-        let length_unit = RapierProjectSettings::get_length_unit();
-        let mut activation_linear_threshold = project_settings
-            .get_setting_with_override(SLEEP_THRESHOLD_LINEAR)
-            .try_to()
-            .unwrap_or_default();
-        activation_linear_threshold /= length_unit;
-        let activation_time_until_sleep = project_settings
-            .get_setting_with_override(TIME_BEFORE_SLEEP)
-            .try_to()
-            .unwrap_or_default();
         Self {
             #[cfg(feature = "dim3")]
             user_flags: 0,
@@ -123,9 +139,10 @@ impl RapierCollisionObjectBase {
             collision_layer: 1,
             is_debugging_contacts: false,
             mode,
-            activation_angular_threshold,
-            activation_linear_threshold,
-            activation_time_until_sleep,
+            activation_angular_threshold: 0.0,
+            activation_linear_threshold: 0.0,
+            activation_time_until_sleep: 0.0,
+            dominance_group: 0,
         }
     }
 
@@ -141,6 +158,7 @@ impl RapierCollisionObjectBase {
         }
         let mut user_data = UserData::default();
         self.set_collider_user_data(&mut user_data, p_shape_index);
+        let mat = shape.collider_material(mat);
         match self.collision_object_type {
             CollisionObjectType::Body => physics_engine.collider_create_solid(
                 self.state.space_id,
@@ -281,6 +299,12 @@ impl RapierCollisionObjectBase {
             self.state.space_id = space.get_state().get_id();
             self.state.space_id = space.get_state().get_id();
             self.is_debugging_contacts = space.is_debugging_contacts();
+            let (angular_threshold, linear_threshold, time_until_sleep) =
+                space.get_body_activation();
+            self.activation_angular_threshold = angular_threshold;
+            self.activation_linear_threshold =
+                linear_threshold / RapierProjectSettings::get_length_unit();
+            self.activation_time_until_sleep = time_until_sleep;
         } else {
             self.state.space_id = WorldHandle::default();
             self.state.space_id = RapierId::default();
@@ -297,6 +321,7 @@ impl RapierCollisionObjectBase {
                 self.activation_angular_threshold,
                 self.activation_linear_threshold,
                 self.activation_time_until_sleep,
+                self.dominance_group,
             );
         } else if self.mode == BodyMode::KINEMATIC {
             self.state.body_handle = physics_engine.body_create(
@@ -307,6 +332,7 @@ impl RapierCollisionObjectBase {
                 self.activation_angular_threshold,
                 self.activation_linear_threshold,
                 self.activation_time_until_sleep,
+                self.dominance_group,
             );
         } else {
             self.state.body_handle = physics_engine.body_create(
@@ -317,6 +343,7 @@ impl RapierCollisionObjectBase {
                 self.activation_angular_threshold,
                 self.activation_linear_threshold,
                 self.activation_time_until_sleep,
+                self.dominance_group,
             );
         }
         let mut user_data = UserData::default();
@@ -476,6 +503,60 @@ impl RapierCollisionObjectBase {
         0.0
     }
 
+    pub fn set_shape_material(&mut self, p_idx: usize, material: CollisionObjectShapeMaterial) {
+        if let Some(shape) = self.state.shapes.get_mut(p_idx) {
+            shape.material = material;
+        }
+    }
+
+    pub fn get_shape_material(&self, p_idx: usize) -> CollisionObjectShapeMaterial {
+        if let Some(shape) = self.state.shapes.get(p_idx) {
+            return shape.material;
+        }
+        CollisionObjectShapeMaterial::default()
+    }
+
+    pub fn set_shape_density(&mut self, p_idx: usize, density: real) {
+        if let Some(shape) = self.state.shapes.get_mut(p_idx) {
+            shape.density = density;
+        }
+    }
+
+    pub fn set_shapes_margin(&mut self, shape_id: RapierId, margin: real) {
+        for shape in self.state.shapes.iter_mut() {
+            if shape.id == shape_id {
+                shape.margin = margin;
+            }
+        }
+    }
+
+    pub fn get_shape_density(&self, p_idx: usize) -> real {
+        if let Some(shape) = self.state.shapes.get(p_idx) {
+            return shape.density;
+        }
+        1.0
+    }
+
+    // Applies the shape material overrides and margins on top of the material of the body.
+    pub(crate) fn update_shapes_material(&self, mat: Material, physics_engine: &mut PhysicsEngine) {
+        if !self.is_valid() {
+            return;
+        }
+        for shape in &self.state.shapes {
+            if (shape.material.is_empty() && shape.margin <= 0.0)
+                || shape.collider_handle == ColliderHandle::invalid()
+            {
+                continue;
+            }
+            let material = shape.collider_material(mat);
+            physics_engine.collider_update_material(
+                self.state.space_id,
+                shape.collider_handle,
+                &material,
+            );
+        }
+    }
+
     pub fn set_collision_mask(&mut self, p_mask: u32, physics_engine: &mut PhysicsEngine) {
         self.collision_mask = p_mask;
         if self.is_valid() {
@@ -2,6 +2,7 @@ use rapier::geometry::ColliderHandle;
 
 use super::rapier_collision_object::IRapierCollisionObject;
 use super::rapier_collision_object_base::CollisionObjectShape;
+use super::rapier_collision_object_base::CollisionObjectShapeMaterial;
 use super::rapier_collision_object_base::RapierCollisionObjectBase;
 use crate::rapier_wrapper::prelude::PhysicsEngine;
 use crate::servers::rapier_physics_singleton::get_id_rid;
@@ -65,7 +66,13 @@ impl RapierCollisionObjectBase {
             one_way_collision: false,
             one_way_collision_margin: 0.0,
             collider_handle: ColliderHandle::invalid(),
+            material: CollisionObjectShapeMaterial::default(),
+            density: 1.0,
+            margin: 0.0,
         };
+        if let Some(shape_ref) = physics_shapes.get(&get_id_rid(p_shape_id, physics_ids)) {
+            shape.margin = shape_ref.get_base().get_margin();
+        }
         if !shape.disabled {
             shape.collider_handle = collision_object.create_shape(
                 shape,
@@ -186,11 +193,14 @@ impl RapierCollisionObjectBase {
                 .remove_owner(collision_object.get_base().get_id());
         }
         collision_object.get_mut_base().state.shapes[p_index].id = p_shape;
-        if let Some(shape) = physics_shapes.get_mut(&get_id_rid(shape.id, physics_ids)) {
+        if let Some(shape) = physics_shapes.get_mut(&get_id_rid(p_shape, physics_ids)) {
             shape
                 .get_mut_base()
                 .add_owner(collision_object.get_base().get_id());
+            collision_object.get_mut_base().state.shapes[p_index].margin =
+                shape.get_base().get_margin();
         }
+        let shape = collision_object.get_base().state.shapes[p_index];
         if !shape.disabled {
             collision_object.get_mut_base().state.shapes[p_index].collider_handle =
                 collision_object.get_base().create_shape(
@@ -14,10 +14,37 @@ pub struct RapierDirectBodyState2D {
     implementation: RapierDirectBodyStateImpl,
     base: Base<PhysicsDirectBodyState2DExtension>,
 }
+#[godot_api]
 impl RapierDirectBodyState2D {
     pub fn set_body(&mut self, body: Rid) {
         self.implementation.set_body(body);
     }
+
+    #[func]
+    /// Returns the magnitude of the friction impulse of the contact.
+    fn get_contact_tangent_impulse(&self, contact_idx: i32) -> real {
+        self.implementation.get_contact_tangent_impulse(contact_idx)
+    }
+
+    #[func]
+    /// Returns the packed feature id (vertex, edge or face) of the body shape at the contact.
+    fn get_contact_local_feature_id(&self, contact_idx: i32) -> u32 {
+        self.implementation
+            .get_contact_local_feature_id(contact_idx)
+    }
+
+    #[func]
+    /// Returns the packed feature id (vertex, edge or face) of the collider shape at the contact.
+    fn get_contact_collider_feature_id(&self, contact_idx: i32) -> u32 {
+        self.implementation
+            .get_contact_collider_feature_id(contact_idx)
+    }
+
+    #[func]
+    /// Returns the friction coefficient used by the solver for the contact.
+    fn get_contact_friction(&self, contact_idx: i32) -> real {
+        self.implementation.get_contact_friction(contact_idx)
+    }
 }
 #[godot_api]
 impl IPhysicsDirectBodyState2DExtension for RapierDirectBodyState2D {
@@ -551,6 +551,54 @@ impl RapierDirectBodyStateImpl {
         Vector::default()
     }
 
+    pub(super) fn get_contact_tangent_impulse(&self, contact_idx: i32) -> real {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&self.body) {
+            if let Some(body) = body.get_body() {
+                if let Some(contact) = body.contacts().get(contact_idx as usize) {
+                    return contact.tangent_impulse;
+                }
+            }
+        }
+        0.0
+    }
+
+    pub(super) fn get_contact_local_feature_id(&self, contact_idx: i32) -> u32 {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&self.body) {
+            if let Some(body) = body.get_body() {
+                if let Some(contact) = body.contacts().get(contact_idx as usize) {
+                    return contact.local_feature_id;
+                }
+            }
+        }
+        0
+    }
+
+    pub(super) fn get_contact_collider_feature_id(&self, contact_idx: i32) -> u32 {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&self.body) {
+            if let Some(body) = body.get_body() {
+                if let Some(contact) = body.contacts().get(contact_idx as usize) {
+                    return contact.collider_feature_id;
+                }
+            }
+        }
+        0
+    }
+
+    pub(super) fn get_contact_friction(&self, contact_idx: i32) -> real {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&self.body) {
+            if let Some(body) = body.get_body() {
+                if let Some(contact) = body.contacts().get(contact_idx as usize) {
+                    return contact.friction;
+                }
+            }
+        }
+        0.0
+    }
+
     pub(super) fn integrate_forces(&mut self) {
         let physics_data = physics_data();
         if let Some(body) = physics_data.collision_objects.get_mut(&self.body) {
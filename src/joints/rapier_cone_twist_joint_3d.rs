@@ -7,20 +7,35 @@ use crate::bodies::rapier_collision_object::RapierCollisionObject;
 use crate::joints::rapier_joint::IRapierJoint;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_physics_singleton::RapierId;
+const DEFAULT_BIAS: f32 = 0.3;
+const DEFAULT_SOFTNESS: f32 = 0.8;
+const DEFAULT_RELAXATION: f32 = 1.0;
 pub struct RapierConeTwistJoint3D {
+    swing_span: f32,
+    twist_span: f32,
+    bias: f32,
+    softness: f32,
+    relaxation: f32,
     base: RapierJointBase,
 }
 impl RapierConeTwistJoint3D {
     pub fn new(
         id: RapierId,
         rid: Rid,
-        anchor_a: Vector3,
-        anchor_b: Vector3,
+        local_ref_a: Transform3D,
+        local_ref_b: Transform3D,
         body_a: &RapierCollisionObject,
         body_b: &RapierCollisionObject,
         physics_engine: &mut PhysicsEngine,
     ) -> Self {
+        let swing_span = std::f32::consts::FRAC_PI_4;
+        let twist_span = std::f32::consts::PI;
         let invalid_joint = Self {
+            swing_span,
+            twist_span,
+            bias: DEFAULT_BIAS,
+            softness: DEFAULT_SOFTNESS,
+            relaxation: DEFAULT_RELAXATION,
             base: RapierJointBase::default(),
         };
         let body_a_rid = body_a.get_base().get_rid();
@@ -34,24 +49,94 @@ impl RapierConeTwistJoint3D {
         {
             return invalid_joint;
         }
-        let rapier_anchor_a = vector_to_rapier(anchor_a);
-        let rapier_anchor_b = vector_to_rapier(anchor_b);
+        let rapier_frame_a = transform_to_rapier(local_ref_a);
+        let rapier_frame_b = transform_to_rapier(local_ref_b);
         let space_handle = body_a.get_base().get_space_id();
         let space_id = body_a.get_base().get_space_id();
-        let handle = physics_engine.joint_create_spherical(
+        let handle = physics_engine.joint_create_cone_twist(
             space_handle,
             body_a.get_base().get_body_handle(),
             body_b.get_base().get_body_handle(),
-            rapier_anchor_a,
-            rapier_anchor_b,
+            rapier_frame_a,
+            rapier_frame_b,
             false,
             false,
             true,
         );
         Self {
+            swing_span,
+            twist_span,
+            bias: DEFAULT_BIAS,
+            softness: DEFAULT_SOFTNESS,
+            relaxation: DEFAULT_RELAXATION,
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle),
         }
     }
+
+    pub fn set_param(
+        &mut self,
+        p_param: physics_server_3d::ConeTwistJointParam,
+        p_value: f32,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        match p_param {
+            physics_server_3d::ConeTwistJointParam::SWING_SPAN => {
+                self.swing_span = p_value;
+            }
+            physics_server_3d::ConeTwistJointParam::TWIST_SPAN => {
+                self.twist_span = p_value;
+            }
+            physics_server_3d::ConeTwistJointParam::BIAS => {
+                self.bias = p_value;
+            }
+            physics_server_3d::ConeTwistJointParam::SOFTNESS => {
+                self.softness = p_value;
+            }
+            physics_server_3d::ConeTwistJointParam::RELAXATION => {
+                self.relaxation = p_value;
+            }
+            _ => {}
+        }
+        if self.base.is_valid() {
+            physics_engine.joint_wake_up_connected_rigidbodies(
+                self.base.get_space_id(),
+                self.base.get_handle(),
+            );
+        }
+    }
+
+    // Called before each step. Rapier limits are hard limits, so the swing and twist limits
+    // are springs that only pull while the joint is past them, the pivot stays hard.
+    // Bias is the part of the error corrected per step, softness scales the stiffness and
+    // relaxation the damping, so the limits change continuously with all three params.
+    pub fn update_limits(&self, step: real, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() || step <= 0.0 {
+            return;
+        }
+        let bias = (self.bias as real).clamp(0.0, 0.99);
+        let softness = (self.softness as real).clamp(0.0, 1.0);
+        let relaxation = (self.relaxation as real).max(0.0);
+        let angular_frequency = bias / ((1.0 - bias) * step);
+        physics_engine.joint_update_cone_twist_limits(
+            self.base.get_space_id(),
+            self.base.get_handle(),
+            self.swing_span,
+            self.twist_span,
+            softness * angular_frequency * angular_frequency,
+            2.0 * relaxation * softness.sqrt() * angular_frequency,
+        );
+    }
+
+    pub fn get_param(&self, p_param: physics_server_3d::ConeTwistJointParam) -> f32 {
+        match p_param {
+            physics_server_3d::ConeTwistJointParam::SWING_SPAN => self.swing_span,
+            physics_server_3d::ConeTwistJointParam::TWIST_SPAN => self.twist_span,
+            physics_server_3d::ConeTwistJointParam::BIAS => self.bias,
+            physics_server_3d::ConeTwistJointParam::SOFTNESS => self.softness,
+            physics_server_3d::ConeTwistJointParam::RELAXATION => self.relaxation,
+            _ => 0.0,
+        }
+    }
 }
 impl IRapierJoint for RapierConeTwistJoint3D {
     fn get_base(&self) -> &RapierJointBase {
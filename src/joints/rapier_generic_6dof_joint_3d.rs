@@ -1,5 +1,9 @@
+use godot::classes::physics_server_3d::G6dofJointAxisFlag;
+use godot::classes::physics_server_3d::G6dofJointAxisParam;
 use godot::classes::*;
 use godot::prelude::*;
+use rapier::dynamics::JointAxesMask;
+use rapier::dynamics::JointAxis;
 
 use super::rapier_joint_base::RapierJointBase;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
@@ -7,20 +11,88 @@ use crate::bodies::rapier_collision_object::RapierCollisionObject;
 use crate::joints::rapier_joint::IRapierJoint;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_physics_singleton::RapierId;
+#[derive(Clone, Copy)]
+struct RapierGeneric6DOFAxis {
+    linear_lower_limit: f32,
+    linear_upper_limit: f32,
+    linear_limit_softness: f32,
+    linear_restitution: f32,
+    linear_damping: f32,
+    linear_motor_target_velocity: f32,
+    linear_motor_force_limit: f32,
+    linear_spring_stiffness: f32,
+    linear_spring_damping: f32,
+    linear_spring_equilibrium_point: f32,
+    angular_lower_limit: f32,
+    angular_upper_limit: f32,
+    angular_limit_softness: f32,
+    angular_damping: f32,
+    angular_restitution: f32,
+    angular_force_limit: f32,
+    angular_erp: f32,
+    angular_motor_target_velocity: f32,
+    angular_motor_force_limit: f32,
+    angular_spring_stiffness: f32,
+    angular_spring_damping: f32,
+    angular_spring_equilibrium_point: f32,
+    linear_limit_enabled: bool,
+    angular_limit_enabled: bool,
+    linear_spring_enabled: bool,
+    angular_spring_enabled: bool,
+    linear_motor_enabled: bool,
+    angular_motor_enabled: bool,
+}
+impl Default for RapierGeneric6DOFAxis {
+    fn default() -> Self {
+        // Same defaults as Godot Physics, all axes start locked.
+        Self {
+            linear_lower_limit: 0.0,
+            linear_upper_limit: 0.0,
+            linear_limit_softness: 0.7,
+            linear_restitution: 0.5,
+            linear_damping: 1.0,
+            linear_motor_target_velocity: 0.0,
+            linear_motor_force_limit: 0.0,
+            linear_spring_stiffness: 0.0,
+            linear_spring_damping: 0.0,
+            linear_spring_equilibrium_point: 0.0,
+            angular_lower_limit: 0.0,
+            angular_upper_limit: 0.0,
+            angular_limit_softness: 0.5,
+            angular_damping: 1.0,
+            angular_restitution: 0.0,
+            angular_force_limit: 0.0,
+            angular_erp: 0.5,
+            angular_motor_target_velocity: 0.0,
+            angular_motor_force_limit: 300.0,
+            angular_spring_stiffness: 0.0,
+            angular_spring_damping: 0.0,
+            angular_spring_equilibrium_point: 0.0,
+            linear_limit_enabled: true,
+            angular_limit_enabled: true,
+            linear_spring_enabled: false,
+            angular_spring_enabled: false,
+            linear_motor_enabled: false,
+            angular_motor_enabled: false,
+        }
+    }
+}
 pub struct RapierGeneric6DOFJoint3D {
+    axes: [RapierGeneric6DOFAxis; 3],
     base: RapierJointBase,
 }
 impl RapierGeneric6DOFJoint3D {
     pub fn new(
         id: RapierId,
         rid: Rid,
-        anchor_a: Vector3,
-        anchor_b: Vector3,
+        local_ref_a: Transform3D,
+        local_ref_b: Transform3D,
         body_a: &RapierCollisionObject,
         body_b: &RapierCollisionObject,
         physics_engine: &mut PhysicsEngine,
     ) -> Self {
         let invalid_joint = Self {
+            axes: [RapierGeneric6DOFAxis::default(); 3],
             base: RapierJointBase::default(),
         };
         let body_a_rid = body_a.get_base().get_rid();
@@ -34,22 +106,224 @@ impl RapierGeneric6DOFJoint3D {
         {
             return invalid_joint;
         }
-        let rapier_anchor_a = vector_to_rapier(anchor_a);
-        let rapier_anchor_b = vector_to_rapier(anchor_b);
+        let rapier_frame_a = transform_to_rapier(local_ref_a);
+        let rapier_frame_b = transform_to_rapier(local_ref_b);
         let space_handle = body_a.get_base().get_space_id();
         let space_id = body_a.get_base().get_space_id();
-        let handle = physics_engine.joint_create_spherical(
+        let handle = physics_engine.joint_create_generic_6dof(
             space_handle,
             body_a.get_base().get_body_handle(),
             body_b.get_base().get_body_handle(),
-            rapier_anchor_a,
-            rapier_anchor_b,
+            rapier_frame_a,
+            rapier_frame_b,
             false,
             false,
             true,
         );
+        let mut base = RapierJointBase::new(id, rid, space_id, space_handle, handle);
+        // Every axis is configured per axis, softness must not override it.
+        base.set_managed_axes(JointAxesMask::all().bits());
         Self {
-            base: RapierJointBase::new(id, rid, space_id, space_handle, handle),
+            axes: [RapierGeneric6DOFAxis::default(); 3],
+            base,
+        }
+    }
+
+    fn axis_index(axis: Vector3Axis) -> usize {
+        match axis {
+            Vector3Axis::X => 0,
+            Vector3Axis::Y => 1,
+            Vector3Axis::Z => 2,
+        }
+    }
+
+    fn update_axis(&mut self, axis: Vector3Axis, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() {
+            return;
+        }
+        let (linear_axis, angular_axis) = match axis {
+            Vector3Axis::X => (JointAxis::LinX, JointAxis::AngX),
+            Vector3Axis::Y => (JointAxis::LinY, JointAxis::AngY),
+            Vector3Axis::Z => (JointAxis::LinZ, JointAxis::AngZ),
+        };
+        let params = self.axes[Self::axis_index(axis)];
+        let handle = physics_engine.joint_change_generic_6dof_axis(
+            self.base.get_space_id(),
+            self.base.get_handle(),
+            linear_axis,
+            params.linear_limit_enabled,
+            params.linear_lower_limit,
+            params.linear_upper_limit,
+            params.linear_motor_enabled,
+            params.linear_motor_target_velocity,
+            params.linear_motor_force_limit,
+            params.linear_spring_enabled,
+            params.linear_spring_stiffness,
+            params.linear_spring_damping,
+            params.linear_spring_equilibrium_point,
+        );
+        let handle = physics_engine.joint_change_generic_6dof_axis(
+            self.base.get_space_id(),
+            handle,
+            angular_axis,
+            params.angular_limit_enabled,
+            params.angular_lower_limit,
+            params.angular_upper_limit,
+            params.angular_motor_enabled,
+            params.angular_motor_target_velocity,
+            params.angular_motor_force_limit,
+            params.angular_spring_enabled,
+            params.angular_spring_stiffness,
+            params.angular_spring_damping,
+            params.angular_spring_equilibrium_point,
+        );
+        self.base.set_handle(handle);
+    }
+
+    pub fn set_param(
+        &mut self,
+        axis: Vector3Axis,
+        p_param: G6dofJointAxisParam,
+        p_value: f32,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        Self::warn_unsupported_param(p_param, p_value);
+        let params = &mut self.axes[Self::axis_index(axis)];
+        match p_param {
+            G6dofJointAxisParam::LINEAR_LOWER_LIMIT => params.linear_lower_limit = p_value,
+            G6dofJointAxisParam::LINEAR_UPPER_LIMIT => params.linear_upper_limit = p_value,
+            G6dofJointAxisParam::LINEAR_LIMIT_SOFTNESS => params.linear_limit_softness = p_value,
+            G6dofJointAxisParam::LINEAR_RESTITUTION => params.linear_restitution = p_value,
+            G6dofJointAxisParam::LINEAR_DAMPING => params.linear_damping = p_value,
+            G6dofJointAxisParam::LINEAR_MOTOR_TARGET_VELOCITY => {
+                params.linear_motor_target_velocity = p_value
+            }
+            G6dofJointAxisParam::LINEAR_MOTOR_FORCE_LIMIT => {
+                params.linear_motor_force_limit = p_value
+            }
+            G6dofJointAxisParam::LINEAR_SPRING_STIFFNESS => {
+                params.linear_spring_stiffness = p_value
+            }
+            G6dofJointAxisParam::LINEAR_SPRING_DAMPING => params.linear_spring_damping = p_value,
+            G6dofJointAxisParam::LINEAR_SPRING_EQUILIBRIUM_POINT => {
+                params.linear_spring_equilibrium_point = p_value
+            }
+            G6dofJointAxisParam::ANGULAR_LOWER_LIMIT => params.angular_lower_limit = p_value,
+            G6dofJointAxisParam::ANGULAR_UPPER_LIMIT => params.angular_upper_limit = p_value,
+            G6dofJointAxisParam::ANGULAR_LIMIT_SOFTNESS => params.angular_limit_softness = p_value,
+            G6dofJointAxisParam::ANGULAR_DAMPING => params.angular_damping = p_value,
+            G6dofJointAxisParam::ANGULAR_RESTITUTION => params.angular_restitution = p_value,
+            G6dofJointAxisParam::ANGULAR_FORCE_LIMIT => params.angular_force_limit = p_value,
+            G6dofJointAxisParam::ANGULAR_ERP => params.angular_erp = p_value,
+            G6dofJointAxisParam::ANGULAR_MOTOR_TARGET_VELOCITY => {
+                params.angular_motor_target_velocity = p_value
+            }
+            G6dofJointAxisParam::ANGULAR_MOTOR_FORCE_LIMIT => {
+                params.angular_motor_force_limit = p_value
+            }
+            G6dofJointAxisParam::ANGULAR_SPRING_STIFFNESS => {
+                params.angular_spring_stiffness = p_value
+            }
+            G6dofJointAxisParam::ANGULAR_SPRING_DAMPING => params.angular_spring_damping = p_value,
+            G6dofJointAxisParam::ANGULAR_SPRING_EQUILIBRIUM_POINT => {
+                params.angular_spring_equilibrium_point = p_value
+            }
+            _ => {}
+        }
+        self.update_axis(axis, physics_engine);
+    }
+
+    // Rapier limits are hard limits solved with the global error correction, and there is no
+    // per-axis restitution or force limit. These params are only stored, so warn when they
+    // are moved away from their defaults.
+    fn warn_unsupported_param(p_param: G6dofJointAxisParam, p_value: f32) {
+        let defaults = RapierGeneric6DOFAxis::default();
+        let default_value = match p_param {
+            G6dofJointAxisParam::LINEAR_LIMIT_SOFTNESS => defaults.linear_limit_softness,
+            G6dofJointAxisParam::LINEAR_RESTITUTION => defaults.linear_restitution,
+            G6dofJointAxisParam::LINEAR_DAMPING => defaults.linear_damping,
+            G6dofJointAxisParam::ANGULAR_LIMIT_SOFTNESS => defaults.angular_limit_softness,
+            G6dofJointAxisParam::ANGULAR_DAMPING => defaults.angular_damping,
+            G6dofJointAxisParam::ANGULAR_RESTITUTION => defaults.angular_restitution,
+            G6dofJointAxisParam::ANGULAR_FORCE_LIMIT => defaults.angular_force_limit,
+            G6dofJointAxisParam::ANGULAR_ERP => defaults.angular_erp,
+            _ => return,
+        };
+        if p_value != default_value {
+            godot_warn!(
+                "Generic6DOFJoint3D param {:?} is not supported by Rapier and has no effect",
+                p_param
+            );
+        }
+    }
+
+    pub fn get_param(&self, axis: Vector3Axis, p_param: G6dofJointAxisParam) -> f32 {
+        let params = &self.axes[Self::axis_index(axis)];
+        match p_param {
+            G6dofJointAxisParam::LINEAR_LOWER_LIMIT => params.linear_lower_limit,
+            G6dofJointAxisParam::LINEAR_UPPER_LIMIT => params.linear_upper_limit,
+            G6dofJointAxisParam::LINEAR_LIMIT_SOFTNESS => params.linear_limit_softness,
+            G6dofJointAxisParam::LINEAR_RESTITUTION => params.linear_restitution,
+            G6dofJointAxisParam::LINEAR_DAMPING => params.linear_damping,
+            G6dofJointAxisParam::LINEAR_MOTOR_TARGET_VELOCITY => {
+                params.linear_motor_target_velocity
+            }
+            G6dofJointAxisParam::LINEAR_MOTOR_FORCE_LIMIT => params.linear_motor_force_limit,
+            G6dofJointAxisParam::LINEAR_SPRING_STIFFNESS => params.linear_spring_stiffness,
+            G6dofJointAxisParam::LINEAR_SPRING_DAMPING => params.linear_spring_damping,
+            G6dofJointAxisParam::LINEAR_SPRING_EQUILIBRIUM_POINT => {
+                params.linear_spring_equilibrium_point
+            }
+            G6dofJointAxisParam::ANGULAR_LOWER_LIMIT => params.angular_lower_limit,
+            G6dofJointAxisParam::ANGULAR_UPPER_LIMIT => params.angular_upper_limit,
+            G6dofJointAxisParam::ANGULAR_LIMIT_SOFTNESS => params.angular_limit_softness,
+            G6dofJointAxisParam::ANGULAR_DAMPING => params.angular_damping,
+            G6dofJointAxisParam::ANGULAR_RESTITUTION => params.angular_restitution,
+            G6dofJointAxisParam::ANGULAR_FORCE_LIMIT => params.angular_force_limit,
+            G6dofJointAxisParam::ANGULAR_ERP => params.angular_erp,
+            G6dofJointAxisParam::ANGULAR_MOTOR_TARGET_VELOCITY => {
+                params.angular_motor_target_velocity
+            }
+            G6dofJointAxisParam::ANGULAR_MOTOR_FORCE_LIMIT => params.angular_motor_force_limit,
+            G6dofJointAxisParam::ANGULAR_SPRING_STIFFNESS => params.angular_spring_stiffness,
+            G6dofJointAxisParam::ANGULAR_SPRING_DAMPING => params.angular_spring_damping,
+            G6dofJointAxisParam::ANGULAR_SPRING_EQUILIBRIUM_POINT => {
+                params.angular_spring_equilibrium_point
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn set_flag(
+        &mut self,
+        axis: Vector3Axis,
+        p_flag: G6dofJointAxisFlag,
+        p_enabled: bool,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        let params = &mut self.axes[Self::axis_index(axis)];
+        match p_flag {
+            G6dofJointAxisFlag::ENABLE_LINEAR_LIMIT => params.linear_limit_enabled = p_enabled,
+            G6dofJointAxisFlag::ENABLE_ANGULAR_LIMIT => params.angular_limit_enabled = p_enabled,
+            G6dofJointAxisFlag::ENABLE_LINEAR_SPRING => params.linear_spring_enabled = p_enabled,
+            G6dofJointAxisFlag::ENABLE_ANGULAR_SPRING => params.angular_spring_enabled = p_enabled,
+            G6dofJointAxisFlag::ENABLE_LINEAR_MOTOR => params.linear_motor_enabled = p_enabled,
+            G6dofJointAxisFlag::ENABLE_MOTOR => params.angular_motor_enabled = p_enabled,
+            _ => {}
+        }
+        self.update_axis(axis, physics_engine);
+    }
+
+    pub fn get_flag(&self, axis: Vector3Axis, p_flag: G6dofJointAxisFlag) -> bool {
+        let params = &self.axes[Self::axis_index(axis)];
+        match p_flag {
+            G6dofJointAxisFlag::ENABLE_LINEAR_LIMIT => params.linear_limit_enabled,
+            G6dofJointAxisFlag::ENABLE_ANGULAR_LIMIT => params.angular_limit_enabled,
+            G6dofJointAxisFlag::ENABLE_LINEAR_SPRING => params.linear_spring_enabled,
+            G6dofJointAxisFlag::ENABLE_ANGULAR_SPRING => params.angular_spring_enabled,
+            G6dofJointAxisFlag::ENABLE_LINEAR_MOTOR => params.linear_motor_enabled,
+            G6dofJointAxisFlag::ENABLE_MOTOR => params.angular_motor_enabled,
+            _ => false,
         }
     }
 }
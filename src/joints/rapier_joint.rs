@@ -11,10 +11,12 @@ use physics_server_3d::JointType;
 use super::rapier_cone_twist_joint_3d::RapierConeTwistJoint3D;
 #[cfg(feature = "dim2")]
 use super::rapier_damped_spring_joint_2d::RapierDampedSpringJoint2D;
+use super::rapier_fixed_joint::RapierFixedJoint;
 #[cfg(feature = "dim3")]
 use super::rapier_generic_6dof_joint_3d::RapierGeneric6DOFJoint3D;
 use super::rapier_joint_base::RapierJointBase;
 use super::rapier_revolute_joint::RapierRevoluteJoint;
+use super::rapier_rope_joint::RapierRopeJoint;
 #[cfg(feature = "dim3")]
 use super::rapier_slider_joint_3d::RapierSliderJoint3D;
 #[cfg(feature = "dim3")]
@@ -26,7 +28,9 @@ pub enum RapierJoint {
     #[cfg(feature = "dim2")]
     RapierGrooveJoint2D(RapierGrooveJoint2D),
     RapierEmptyJoint(RapierEmptyJoint),
+    RapierFixedJoint(RapierFixedJoint),
     RapierRevoluteJoint(RapierRevoluteJoint),
+    RapierRopeJoint(RapierRopeJoint),
     #[cfg(feature = "dim3")]
     RapierSliderJoint3D(RapierSliderJoint3D),
     #[cfg(feature = "dim3")]
@@ -63,7 +67,9 @@ macro_rules! impl_rapier_joint_trait {
 impl_rapier_joint_trait!(
     RapierJoint,
     RapierEmptyJoint,
+    RapierFixedJoint,
     RapierRevoluteJoint,
+    RapierRopeJoint,
     RapierSliderJoint3D,
     RapierConeTwistJoint3D,
     RapierSphericalJoint3D,
@@ -75,7 +81,9 @@ impl_rapier_joint_trait!(
     RapierDampedSpringJoint2D,
     RapierGrooveJoint2D,
     RapierEmptyJoint,
-    RapierRevoluteJoint
+    RapierFixedJoint,
+    RapierRevoluteJoint,
+    RapierRopeJoint
 );
 pub trait IRapierJoint {
     fn get_base(&self) -> &RapierJointBase;
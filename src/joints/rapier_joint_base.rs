@@ -1,8 +1,12 @@
+use godot::builtin::real_consts;
 use servers::rapier_physics_singleton::get_id_rid;
 use servers::rapier_physics_singleton::PhysicsIds;
 use servers::rapier_physics_singleton::RapierId;
+use servers::rapier_project_settings::RapierProjectSettings;
 
 use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_server_extra::RapierJointParam;
+use crate::types::*;
 use crate::*;
 #[cfg_attr(
     feature = "serde-serialize",
@@ -14,11 +18,66 @@ pub struct RapierJointBaseState {
     handle: JointHandle,
     space_handle: WorldHandle,
     space_id: RapierId,
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(default = "default_solver_priority")
+    )]
+    solver_priority: i32,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    natural_frequency: Option<real>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    damping_ratio: Option<real>,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    softened_axes: u8,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    type_spring: Option<RapierJointSpring>,
+}
+// Spring holding the locked axes of a joint type that is soft by its own parameters.
+#[cfg_attr(
+    feature = "serde-serialize",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[derive(Default, Debug, PartialEq, Clone, Copy)]
+pub struct RapierJointSpring {
+    pub stiffness: real,
+    pub damping: real,
+    pub max_force: real,
+}
+impl RapierJointSpring {
+    // Spring for acceleration based motors that corrects the same part of the velocity and
+    // position error per step as the damping and bias of a Godot joint. Full damping is a
+    // rigid constraint and returns None, the spring gets stiffer continuously towards it.
+    pub fn from_bias(bias: real, damping: real, max_force: real, step: real) -> Option<Self> {
+        let damping = damping.clamp(0.0, 1.0);
+        if damping >= 1.0 || step <= 0.0 {
+            return None;
+        }
+        // h^2 * k + h * c, the velocity correction per step is correction / (1 + correction).
+        let correction = damping / (1.0 - damping);
+        // Part of the position error corrected on top of the velocity correction.
+        let mut erp = 0.0;
+        if damping > 0.0 {
+            erp = (bias.max(0.0) / damping).min(1.0);
+        }
+        Some(Self {
+            stiffness: erp * correction / (step * step),
+            damping: (1.0 - erp) * correction / step,
+            max_force,
+        })
+    }
+}
+#[cfg(feature = "serde-serialize")]
+fn default_solver_priority() -> i32 {
+    1
 }
 pub struct RapierJointBase {
     rid: Rid,
     max_force: f32,
+    broken: bool,
+    multibody: bool,
     disabled_collisions_between_bodies: bool,
+    // Axes the joint type locks, limits or drives itself, softness never touches them.
+    managed_axes: u8,
     state: RapierJointBaseState,
 }
 impl Default for RapierJointBase {
@@ -43,12 +102,19 @@ impl RapierJointBase {
         Self {
             rid,
             max_force: f32::MAX,
+            broken: false,
+            multibody: false,
             disabled_collisions_between_bodies: true,
+            managed_axes: 0,
             state: RapierJointBaseState {
                 id,
                 handle,
                 space_handle,
                 space_id,
+                solver_priority: 1,
+                natural_frequency: None,
+                damping_ratio: None,
+                softened_axes: 0,
             },
         }
     }
@@ -81,6 +147,155 @@ impl RapierJointBase {
         self.max_force
     }
 
+    pub fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    // Called after a step when the joint impulse went over max_force * dt.
+    // The engine already disabled the joint, it stays in the world until it gets recreated.
+    pub fn set_broken(&mut self) {
+        self.broken = true;
+    }
+
+    pub fn set_multibody(&mut self, multibody: bool, physics_engine: &mut PhysicsEngine) {
+        if !self.is_valid() {
+            self.multibody = multibody;
+            return;
+        }
+        // The joint falls back to its previous mode if it can't be inserted in the new one.
+        self.state.handle = physics_engine.joint_change_multibody(
+            self.state.space_handle,
+            self.state.handle,
+            multibody,
+        );
+        self.multibody = self.state.handle.multibody;
+    }
+
+    pub fn is_multibody(&self) -> bool {
+        self.multibody
+    }
+
+    pub fn set_solver_priority(&mut self, priority: i32) {
+        self.state.solver_priority = priority;
+    }
+
+    pub fn get_solver_priority(&self) -> i32 {
+        self.state.solver_priority
+    }
+
+    pub fn set_managed_axes(&mut self, managed_axes: u8) {
+        self.managed_axes = managed_axes;
+    }
+
+    pub fn set_handle(&mut self, handle: JointHandle) {
+        self.state.handle = handle;
+    }
+
+    // Overrides of the joint_natural_frequency and joint_damping_ratio project settings for this joint.
+    pub fn set_softness(
+        &mut self,
+        natural_frequency: Option<real>,
+        damping_ratio: Option<real>,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.state.natural_frequency = natural_frequency;
+        self.state.damping_ratio = damping_ratio;
+        self.update_softness(physics_engine);
+    }
+
+    // Used by joint types whose own parameters make them soft. The softness set through the
+    // extra params takes precedence over it.
+    pub fn set_type_spring(
+        &mut self,
+        spring: Option<RapierJointSpring>,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.state.type_spring = spring;
+        self.update_softness(physics_engine);
+    }
+
+    pub fn get_type_spring(&self) -> Option<RapierJointSpring> {
+        self.state.type_spring
+    }
+
+    fn update_softness(&mut self, physics_engine: &mut PhysicsEngine) {
+        if !self.is_valid() {
+            return;
+        }
+        let spring = if self.state.natural_frequency.is_some() || self.state.damping_ratio.is_some()
+        {
+            let natural_frequency = self
+                .state
+                .natural_frequency
+                .unwrap_or(RapierProjectSettings::get_joint_natural_frequency());
+            let damping_ratio = self
+                .state
+                .damping_ratio
+                .unwrap_or(RapierProjectSettings::get_joint_damping_ratio());
+            let angular_frequency = natural_frequency * 2.0 * real_consts::PI;
+            Some(RapierJointSpring {
+                stiffness: angular_frequency * angular_frequency,
+                damping: 2.0 * damping_ratio * angular_frequency,
+                max_force: real::MAX,
+            })
+        } else {
+            self.state.type_spring
+        };
+        let soft = spring.is_some();
+        let spring = spring.unwrap_or_default();
+        (self.state.handle, self.state.softened_axes) = physics_engine.joint_change_softness(
+            self.state.space_handle,
+            self.state.handle,
+            self.state.softened_axes,
+            self.managed_axes,
+            soft,
+            spring.stiffness,
+            spring.damping,
+            spring.max_force,
+        );
+    }
+
+    pub fn set_extra_param(
+        &mut self,
+        p_param: RapierJointParam,
+        p_value: Variant,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        match p_param {
+            RapierJointParam::Multibody => {
+                if p_value.get_type() != VariantType::BOOL {
+                    return;
+                }
+                self.set_multibody(p_value.booleanize(), physics_engine);
+            }
+            RapierJointParam::SolverPriority => {
+                if p_value.get_type() != VariantType::INT {
+                    return;
+                }
+                self.set_solver_priority(p_value.to());
+            }
+            RapierJointParam::NaturalFrequency => {
+                let natural_frequency = variant_to_optional_float(&p_value);
+                self.set_softness(natural_frequency, self.state.damping_ratio, physics_engine);
+            }
+            RapierJointParam::DampingRatio => {
+                let damping_ratio = variant_to_optional_float(&p_value);
+                self.set_softness(self.state.natural_frequency, damping_ratio, physics_engine);
+            }
+        }
+    }
+
+    pub fn get_extra_param(&self, p_param: RapierJointParam) -> Variant {
+        match p_param {
+            RapierJointParam::Multibody => self.is_multibody().to_variant(),
+            RapierJointParam::SolverPriority => self.get_solver_priority().to_variant(),
+            RapierJointParam::NaturalFrequency => {
+                optional_float_to_variant(self.state.natural_frequency)
+            }
+            RapierJointParam::DampingRatio => optional_float_to_variant(self.state.damping_ratio),
+        }
+    }
+
     pub fn is_valid(&self) -> bool {
         self.state.space_handle != WorldHandle::default()
             && self.state.handle != JointHandle::default()
@@ -111,6 +326,17 @@ impl RapierJointBase {
         physics_engine: &mut PhysicsEngine,
     ) {
         self.set_max_force(joint.get_max_force());
+        if joint.is_multibody() {
+            self.set_multibody(true, physics_engine);
+        }
+        self.set_solver_priority(joint.get_solver_priority());
+        if joint.state.natural_frequency.is_some() || joint.state.damping_ratio.is_some() {
+            self.set_softness(
+                joint.state.natural_frequency,
+                joint.state.damping_ratio,
+                physics_engine,
+            );
+        }
         self.disable_collisions_between_bodies(
             joint.is_disabled_collisions_between_bodies(),
             physics_engine,
@@ -157,6 +383,7 @@ impl RapierJointBase {
         match bincode::deserialize::<RapierJointBaseState>(data.as_slice()) {
             Ok(import) => {
                 self.state = import;
+                self.multibody = self.state.handle.multibody;
             }
             Err(e) => {
                 godot_error!("Failed to deserialize joint from binary: {}", e);
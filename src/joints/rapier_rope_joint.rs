@@ -0,0 +1,139 @@
+use godot::classes::*;
+#[cfg(feature = "dim2")]
+use physics_server_2d::JointType;
+#[cfg(feature = "dim3")]
+use physics_server_3d::JointType;
+
+use super::rapier_joint_base::RapierJointBase;
+use crate::bodies::rapier_collision_object::IRapierCollisionObject;
+use crate::bodies::rapier_collision_object::RapierCollisionObject;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::types::*;
+use crate::*;
+pub struct RapierRopeJoint {
+    min_length: real,
+    max_length: real,
+    compliance: real,
+    base: RapierJointBase,
+}
+impl RapierRopeJoint {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: RapierId,
+        rid: Rid,
+        anchor_a: Vector,
+        anchor_b: Vector,
+        max_length: real,
+        body_a: &RapierCollisionObject,
+        body_b: &RapierCollisionObject,
+        physics_engine: &mut PhysicsEngine,
+    ) -> Self {
+        let max_length = max_length.max(0.0);
+        let invalid_joint = Self {
+            min_length: 0.0,
+            max_length,
+            compliance: 0.0,
+            base: RapierJointBase::default(),
+        };
+        let body_a_rid = body_a.get_base().get_rid();
+        let body_b_rid = body_b.get_base().get_rid();
+        if body_a_rid == body_b_rid {
+            return invalid_joint;
+        }
+        if !body_a.get_base().is_valid()
+            || !body_b.get_base().is_valid()
+            || body_a.get_base().get_space_id() != body_b.get_base().get_space_id()
+        {
+            return invalid_joint;
+        }
+        let space_handle = body_a.get_base().get_space_id();
+        let space_id = body_a.get_base().get_space_id();
+        let handle = physics_engine.joint_create_rope(
+            space_handle,
+            body_a.get_base().get_body_handle(),
+            body_b.get_base().get_body_handle(),
+            vector_to_rapier(anchor_a),
+            vector_to_rapier(anchor_b),
+            0.0,
+            max_length,
+            false,
+            false,
+            true,
+        );
+        Self {
+            min_length: 0.0,
+            max_length,
+            compliance: 0.0,
+            base: RapierJointBase::new(id, rid, space_id, space_handle, handle),
+        }
+    }
+
+    pub fn set_min_length(&mut self, min_length: real, physics_engine: &mut PhysicsEngine) {
+        self.min_length = min_length.clamp(0.0, self.max_length);
+        self.update_params(physics_engine);
+    }
+
+    pub fn get_min_length(&self) -> real {
+        self.min_length
+    }
+
+    pub fn set_max_length(&mut self, max_length: real, physics_engine: &mut PhysicsEngine) {
+        self.max_length = max_length.max(0.0);
+        self.min_length = self.min_length.min(self.max_length);
+        self.update_params(physics_engine);
+    }
+
+    pub fn get_max_length(&self) -> real {
+        self.max_length
+    }
+
+    pub fn set_compliance(&mut self, compliance: real, physics_engine: &mut PhysicsEngine) {
+        self.compliance = compliance.max(0.0);
+        self.update_params(physics_engine);
+    }
+
+    pub fn get_compliance(&self) -> real {
+        self.compliance
+    }
+
+    fn update_params(&self, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() {
+            return;
+        }
+        physics_engine.joint_change_rope_params(
+            self.base.get_space_id(),
+            self.base.get_handle(),
+            self.min_length,
+            self.max_length,
+            self.compliance,
+        );
+    }
+
+    // Called before each step, a compliant rope only pulls while stretched.
+    pub fn update_spring(&self, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() || self.compliance <= 0.0 {
+            return;
+        }
+        physics_engine.joint_update_rope_spring(
+            self.base.get_space_id(),
+            self.base.get_handle(),
+            self.max_length,
+            self.compliance,
+        );
+    }
+}
+impl IRapierJoint for RapierRopeJoint {
+    fn get_base(&self) -> &RapierJointBase {
+        &self.base
+    }
+
+    fn get_mut_base(&mut self) -> &mut RapierJointBase {
+        &mut self.base
+    }
+
+    fn get_type(&self) -> JointType {
+        JointType::MAX
+    }
+}
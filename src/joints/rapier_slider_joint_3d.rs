@@ -1,5 +1,7 @@
 use godot::classes::*;
 use godot::prelude::*;
+use rapier::dynamics::JointAxesMask;
+use rapier::dynamics::JointAxis;
 
 use super::rapier_joint_base::RapierJointBase;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
@@ -7,10 +9,37 @@ use crate::bodies::rapier_collision_object::RapierCollisionObject;
 use crate::joints::rapier_joint::IRapierJoint;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_physics_singleton::RapierId;
+use crate::spaces::rapier_space::RapierSpace;
+const SLIDER_JOINT_PARAM_COUNT: usize = 22;
+#[derive(Clone, Copy, PartialEq)]
+pub struct RapierSliderJointMotor {
+    pub enabled: bool,
+    pub target_velocity: f32,
+    pub target_position: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+    pub max_force: f32,
+}
+impl Default for RapierSliderJointMotor {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_velocity: 0.0,
+            target_position: 0.0,
+            stiffness: 0.0,
+            damping: 0.0,
+            max_force: f32::MAX,
+        }
+    }
+}
 pub struct RapierSliderJoint3D {
     base: RapierJointBase,
-    linear_limit_lower: f32,
-    linear_limit_upper: f32,
+    params: [f32; SLIDER_JOINT_PARAM_COUNT],
+    linear_motor: RapierSliderJointMotor,
+    angular_motor: RapierSliderJointMotor,
+    // The motors last applied to the slide axis and the rotation around it.
+    linear_axis_motor: RapierSliderJointMotor,
+    angular_axis_motor: RapierSliderJointMotor,
 }
 impl RapierSliderJoint3D {
     pub fn new(
@@ -24,8 +53,11 @@ impl RapierSliderJoint3D {
     ) -> Self {
         let invalid_joint = Self {
             base: RapierJointBase::default(),
-            linear_limit_lower: 0.0,
-            linear_limit_upper: 0.0,
+            params: Self::default_params(),
+            linear_motor: RapierSliderJointMotor::default(),
+            angular_motor: RapierSliderJointMotor::default(),
+            linear_axis_motor: RapierSliderJointMotor::default(),
+            angular_axis_motor: RapierSliderJointMotor::default(),
         };
         let body_a_rid = body_a.get_base().get_rid();
         let body_b_rid = body_b.get_base().get_rid();
@@ -54,47 +86,208 @@ impl RapierSliderJoint3D {
             false,
             true,
         );
+        let mut base = RapierJointBase::new(id, rid, space_id, space_handle, handle);
+        // The slide axis and its rotation are driven by the slider limits and motors.
+        base.set_managed_axes((JointAxesMask::LIN_X | JointAxesMask::ANG_X).bits());
         Self {
-            base: RapierJointBase::new(id, rid, space_id, space_handle, handle),
-            linear_limit_lower: 0.0,
-            linear_limit_upper: 0.0,
+            base,
+            params: Self::default_params(),
+            linear_motor: RapierSliderJointMotor::default(),
+            angular_motor: RapierSliderJointMotor::default(),
+            linear_axis_motor: RapierSliderJointMotor::default(),
+            angular_axis_motor: RapierSliderJointMotor::default(),
+        }
+    }
+
+    fn default_params() -> [f32; SLIDER_JOINT_PARAM_COUNT] {
+        use physics_server_3d::SliderJointParam;
+        let mut params = [0.0; SLIDER_JOINT_PARAM_COUNT];
+        for (param, value) in [
+            (SliderJointParam::LINEAR_LIMIT_SOFTNESS, 1.0),
+            (SliderJointParam::LINEAR_LIMIT_RESTITUTION, 0.7),
+            (SliderJointParam::LINEAR_LIMIT_DAMPING, 1.0),
+            (SliderJointParam::LINEAR_MOTION_SOFTNESS, 1.0),
+            (SliderJointParam::LINEAR_MOTION_RESTITUTION, 0.7),
+            (SliderJointParam::LINEAR_ORTHOGONAL_SOFTNESS, 1.0),
+            (SliderJointParam::LINEAR_ORTHOGONAL_RESTITUTION, 0.7),
+            (SliderJointParam::LINEAR_ORTHOGONAL_DAMPING, 1.0),
+            (SliderJointParam::ANGULAR_LIMIT_SOFTNESS, 1.0),
+            (SliderJointParam::ANGULAR_LIMIT_RESTITUTION, 0.7),
+            (SliderJointParam::ANGULAR_MOTION_SOFTNESS, 1.0),
+            (SliderJointParam::ANGULAR_MOTION_RESTITUTION, 0.7),
+            (SliderJointParam::ANGULAR_MOTION_DAMPING, 1.0),
+            (SliderJointParam::ANGULAR_ORTHOGONAL_SOFTNESS, 1.0),
+            (SliderJointParam::ANGULAR_ORTHOGONAL_RESTITUTION, 0.7),
+            (SliderJointParam::ANGULAR_ORTHOGONAL_DAMPING, 1.0),
+        ] {
+            params[param.ord() as usize] = value;
         }
+        params
     }
 
-    #[cfg(feature = "dim3")]
     pub fn set_param(
         &mut self,
         p_param: physics_server_3d::SliderJointParam,
         p_value: f32,
         physics_engine: &mut PhysicsEngine,
     ) {
-        match p_param {
-            physics_server_3d::SliderJointParam::LINEAR_LIMIT_UPPER => {
-                self.linear_limit_upper = p_value
-            }
-            physics_server_3d::SliderJointParam::LINEAR_LIMIT_LOWER => {
-                self.linear_limit_lower = p_value
-            }
-            _ => {}
-        }
+        let Some(param) = self.params.get_mut(p_param.ord() as usize) else {
+            return;
+        };
+        *param = p_value;
+        Self::warn_unsupported_param(p_param, p_value);
         if !self.base.is_valid() {
             return;
         }
-        physics_engine.joint_change_slider(
+        let handle = physics_engine.joint_change_slider(
             self.base.get_space_id(),
             self.base.get_handle(),
-            self.linear_limit_lower,
-            self.linear_limit_upper,
+            self.get_param(physics_server_3d::SliderJointParam::LINEAR_LIMIT_LOWER),
+            self.get_param(physics_server_3d::SliderJointParam::LINEAR_LIMIT_UPPER),
+            self.get_param(physics_server_3d::SliderJointParam::ANGULAR_LIMIT_LOWER),
+            self.get_param(physics_server_3d::SliderJointParam::ANGULAR_LIMIT_UPPER),
         );
+        self.base.set_handle(handle);
+        self.update_motors(RapierSpace::get_last_step(), physics_engine);
     }
 
-    pub fn get_param(&self, p_param: physics_server_3d::SliderJointParam) -> f32 {
+    // Rapier limits are hard limits, and the orthogonal axes are locked. The limit and orthogonal
+    // params are only stored, so warn when they are moved away from their defaults. The motion
+    // restitution has no effect in Godot either, the free motion has no depth to restore.
+    fn warn_unsupported_param(p_param: physics_server_3d::SliderJointParam, p_value: f32) {
+        use physics_server_3d::SliderJointParam;
         match p_param {
-            physics_server_3d::SliderJointParam::LINEAR_LIMIT_UPPER => self.linear_limit_upper,
-            physics_server_3d::SliderJointParam::LINEAR_LIMIT_LOWER => self.linear_limit_lower,
-            _ => 0.0,
+            SliderJointParam::LINEAR_LIMIT_SOFTNESS
+            | SliderJointParam::LINEAR_LIMIT_RESTITUTION
+            | SliderJointParam::LINEAR_LIMIT_DAMPING
+            | SliderJointParam::LINEAR_ORTHOGONAL_SOFTNESS
+            | SliderJointParam::LINEAR_ORTHOGONAL_RESTITUTION
+            | SliderJointParam::LINEAR_ORTHOGONAL_DAMPING
+            | SliderJointParam::ANGULAR_LIMIT_SOFTNESS
+            | SliderJointParam::ANGULAR_LIMIT_RESTITUTION
+            | SliderJointParam::ANGULAR_LIMIT_DAMPING
+            | SliderJointParam::ANGULAR_ORTHOGONAL_SOFTNESS
+            | SliderJointParam::ANGULAR_ORTHOGONAL_RESTITUTION
+            | SliderJointParam::ANGULAR_ORTHOGONAL_DAMPING => {}
+            _ => return,
+        }
+        if p_value != Self::default_params()[p_param.ord() as usize] {
+            godot_warn!(
+                "SliderJoint3D param {:?} is not supported by Rapier and has no effect",
+                p_param
+            );
         }
     }
+
+    // Also called before each step, so the motion damping follows the actual step.
+    pub fn update_motors(&mut self, step: real, physics_engine: &mut PhysicsEngine) {
+        use physics_server_3d::SliderJointParam;
+        let linear_axis_motor = self.motion_motor(
+            self.linear_motor,
+            SliderJointParam::LINEAR_MOTION_SOFTNESS,
+            SliderJointParam::LINEAR_MOTION_DAMPING,
+            step,
+        );
+        if linear_axis_motor != self.linear_axis_motor {
+            self.linear_axis_motor = linear_axis_motor;
+            self.update_motor(JointAxis::LinX, linear_axis_motor, physics_engine);
+        }
+        let angular_axis_motor = self.motion_motor(
+            self.angular_motor,
+            SliderJointParam::ANGULAR_MOTION_SOFTNESS,
+            SliderJointParam::ANGULAR_MOTION_DAMPING,
+            step,
+        );
+        if angular_axis_motor != self.angular_axis_motor {
+            self.angular_axis_motor = angular_axis_motor;
+            self.update_motor(JointAxis::AngX, angular_axis_motor, physics_engine);
+        }
+    }
+
+    // Godot removes softness * damping of the relative velocity of the free motion each step.
+    // While the motor of the axis is off, a motor with a zero target velocity removes the same
+    // fraction. An acceleration based motor with a damping c removes c * dt / (1 + c * dt) of it,
+    // and a zero damping makes it a rigid velocity motor, which removes all of it.
+    fn motion_motor(
+        &self,
+        motor: RapierSliderJointMotor,
+        softness: physics_server_3d::SliderJointParam,
+        damping: physics_server_3d::SliderJointParam,
+        step: real,
+    ) -> RapierSliderJointMotor {
+        if motor.enabled || step <= 0.0 {
+            return motor;
+        }
+        let fraction = (self.get_param(softness) * self.get_param(damping)).clamp(0.0, 1.0);
+        if fraction <= 0.0 {
+            return motor;
+        }
+        let damping = if fraction >= 1.0 {
+            0.0
+        } else {
+            fraction / ((1.0 - fraction) * step as f32)
+        };
+        RapierSliderJointMotor {
+            enabled: true,
+            damping,
+            ..Default::default()
+        }
+    }
+
+    pub fn get_param(&self, p_param: physics_server_3d::SliderJointParam) -> f32 {
+        self.params
+            .get(p_param.ord() as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_linear_motor(
+        &mut self,
+        motor: RapierSliderJointMotor,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.linear_motor = motor;
+        self.update_motors(RapierSpace::get_last_step(), physics_engine);
+    }
+
+    pub fn get_linear_motor(&self) -> RapierSliderJointMotor {
+        self.linear_motor
+    }
+
+    pub fn set_angular_motor(
+        &mut self,
+        motor: RapierSliderJointMotor,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        self.angular_motor = motor;
+        self.update_motors(RapierSpace::get_last_step(), physics_engine);
+    }
+
+    pub fn get_angular_motor(&self) -> RapierSliderJointMotor {
+        self.angular_motor
+    }
+
+    fn update_motor(
+        &self,
+        axis: JointAxis,
+        motor: RapierSliderJointMotor,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        if !self.base.is_valid() {
+            return;
+        }
+        physics_engine.joint_change_slider_motor(
+            self.base.get_space_id(),
+            self.base.get_handle(),
+            axis,
+            motor.enabled,
+            motor.target_velocity,
+            motor.target_position,
+            motor.stiffness,
+            motor.damping,
+            motor.max_force,
+        );
+    }
 }
 impl IRapierJoint for RapierSliderJoint3D {
     fn get_base(&self) -> &RapierJointBase {
@@ -2,14 +2,22 @@ use godot::classes::*;
 use godot::prelude::*;
 
 use super::rapier_joint_base::RapierJointBase;
+use super::rapier_joint_base::RapierJointSpring;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
 use crate::bodies::rapier_collision_object::RapierCollisionObject;
 use crate::joints::rapier_joint::IRapierJoint;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_physics_singleton::RapierId;
+use crate::spaces::rapier_space::RapierSpace;
+const DEFAULT_BIAS: f32 = 0.3;
+const DEFAULT_DAMPING: f32 = 1.0;
+const DEFAULT_IMPULSE_CLAMP: f32 = 0.0;
 pub struct RapierSphericalJoint3D {
     anchor_a: Vector3,
     anchor_b: Vector3,
+    bias: f32,
+    damping: f32,
+    impulse_clamp: f32,
     base: RapierJointBase,
 }
 impl RapierSphericalJoint3D {
@@ -25,6 +33,9 @@ impl RapierSphericalJoint3D {
         let invalid_joint = Self {
             anchor_a,
             anchor_b,
+            bias: DEFAULT_BIAS,
+            damping: DEFAULT_DAMPING,
+            impulse_clamp: DEFAULT_IMPULSE_CLAMP,
             base: RapierJointBase::default(),
         };
         let body_a_rid = body_a.get_base().get_rid();
@@ -55,6 +66,9 @@ impl RapierSphericalJoint3D {
         Self {
             anchor_a,
             anchor_b,
+            bias: DEFAULT_BIAS,
+            damping: DEFAULT_DAMPING,
+            impulse_clamp: DEFAULT_IMPULSE_CLAMP,
             base: RapierJointBase::new(id, rid, space_id, space_handle, handle),
         }
     }
@@ -89,6 +103,55 @@ impl RapierSphericalJoint3D {
         );
     }
 
+    pub fn set_param(
+        &mut self,
+        p_param: physics_server_3d::PinJointParam,
+        p_value: f32,
+        physics_engine: &mut PhysicsEngine,
+    ) {
+        match p_param {
+            physics_server_3d::PinJointParam::BIAS => {
+                self.bias = p_value.clamp(0.0, 0.99);
+            }
+            physics_server_3d::PinJointParam::DAMPING => {
+                self.damping = p_value.max(0.0);
+            }
+            physics_server_3d::PinJointParam::IMPULSE_CLAMP => {
+                self.impulse_clamp = p_value.max(0.0);
+            }
+            _ => {}
+        }
+        self.update_spring(RapierSpace::get_last_step(), physics_engine);
+    }
+
+    // Also called before each step, so the spring follows the actual step.
+    // Bias and damping set the part of the position and velocity error corrected per step,
+    // with full damping the pin is a hard constraint. Rapier can't bound the impulse of a
+    // locked axis, so the impulse clamp only bounds the spring of a soft pin.
+    pub fn update_spring(&mut self, step: real, physics_engine: &mut PhysicsEngine) {
+        if !self.base.is_valid() {
+            return;
+        }
+        let mut max_force = real::MAX;
+        if self.impulse_clamp > 0.0 && step > 0.0 {
+            max_force = self.impulse_clamp as real / step;
+        }
+        let spring =
+            RapierJointSpring::from_bias(self.bias as real, self.damping as real, max_force, step);
+        if spring != self.base.get_type_spring() {
+            self.base.set_type_spring(spring, physics_engine);
+        }
+    }
+
+    pub fn get_param(&self, p_param: physics_server_3d::PinJointParam) -> f32 {
+        match p_param {
+            physics_server_3d::PinJointParam::BIAS => self.bias,
+            physics_server_3d::PinJointParam::DAMPING => self.damping,
+            physics_server_3d::PinJointParam::IMPULSE_CLAMP => self.impulse_clamp,
+            _ => 0.0,
+        }
+    }
+
     pub fn get_anchor_a(&self) -> Vector3 {
         self.anchor_a
     }
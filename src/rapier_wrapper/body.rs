@@ -53,6 +53,7 @@ impl PhysicsEngine {
         activation_angular_threshold: Real,
         activation_linear_threshold: Real,
         activation_time_until_sleep: Real,
+        dominance_group: i8,
     ) -> RigidBodyHandle {
         let Some(physics_world) = self.get_mut_world(world_handle) else {
             return RigidBodyHandle::invalid();
@@ -73,6 +74,7 @@ impl PhysicsEngine {
         activation.angular_threshold = activation_angular_threshold;
         activation.normalized_linear_threshold = activation_linear_threshold;
         activation.time_until_sleep = activation_time_until_sleep;
+        rigid_body.set_dominance_group(dominance_group);
         set_rigid_body_properties_internal(
             &mut rigid_body,
             Translation::from(pos),
@@ -107,6 +109,7 @@ impl PhysicsEngine {
         world_handle: WorldHandle,
         body_handle: RigidBodyHandle,
         body_type: BodyType,
+        dominance_group: i8,
         wakeup: bool,
     ) {
         if let Some(physics_world) = self.get_mut_world(world_handle)
@@ -126,6 +129,24 @@ impl PhysicsEngine {
                     body.set_body_type(RigidBodyType::Fixed, wakeup);
                 }
             }
+            body.set_dominance_group(dominance_group);
+        }
+        self.body_wake_up_connected_rigidbodies(world_handle, body_handle);
+    }
+
+    pub fn body_set_dominance_group(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+        dominance_group: i8,
+    ) {
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(body) = physics_world
+                .physics_objects
+                .rigid_body_set
+                .get_mut(body_handle)
+        {
+            body.set_dominance_group(dominance_group);
         }
         self.body_wake_up_connected_rigidbodies(world_handle, body_handle);
     }
@@ -266,23 +287,7 @@ impl PhysicsEngine {
                     .collider_set
                     .get_mut(*collider)
                 {
-                    if let Some(friction) = mat.friction {
-                        col.set_friction(friction);
-                    }
-                    if let Some(restitution) = mat.restitution {
-                        col.set_restitution(restitution);
-                    }
-                    if let Some(contact_skin) = mat.contact_skin {
-                        col.set_contact_skin(contact_skin);
-                    }
-                    if let Some(collision_mask) = mat.collision_mask
-                        && let Some(collision_layer) = mat.collision_layer
-                    {
-                        col.set_collision_groups(InteractionGroups {
-                            memberships: Group::from(collision_layer),
-                            filter: Group::from(collision_mask),
-                        });
-                    }
+                    apply_material(col, mat);
                 }
             }
             body.wake_up(false);
@@ -430,6 +435,7 @@ impl PhysicsEngine {
         world_handle: WorldHandle,
         body_handle: RigidBodyHandle,
         enable: bool,
+        soft_ccd_prediction: Real,
     ) {
         if let Some(physics_world) = self.get_mut_world(world_handle)
             && let Some(body) = physics_world
@@ -438,6 +444,7 @@ impl PhysicsEngine {
                 .get_mut(body_handle)
         {
             body.enable_ccd(enable);
+            body.set_soft_ccd_prediction(soft_ccd_prediction);
         }
         self.body_wake_up_connected_rigidbodies(world_handle, body_handle);
     }
@@ -487,6 +494,38 @@ impl PhysicsEngine {
         self.body_wake_up_connected_rigidbodies(world_handle, body_handle);
     }
 
+    // Lets Rapier compute the mass properties from the collider densities.
+    pub fn body_get_density_mass_properties(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+        densities: &[(ColliderHandle, Real)],
+        additional_mass: Real,
+    ) -> RigidBodyMassProps {
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(body) = physics_world
+                .physics_objects
+                .rigid_body_set
+                .get_mut(body_handle)
+        {
+            for (collider_handle, density) in densities {
+                if let Some(collider) = physics_world
+                    .physics_objects
+                    .collider_set
+                    .get_mut(*collider_handle)
+                {
+                    collider.set_density(*density);
+                }
+            }
+            body.set_additional_mass(additional_mass, false);
+            body.recompute_mass_properties_from_colliders(
+                &physics_world.physics_objects.collider_set,
+            );
+            return body.mass_properties().clone();
+        }
+        RigidBodyMassProps::default()
+    }
+
     pub fn body_add_force(
         &mut self,
         world_handle: WorldHandle,
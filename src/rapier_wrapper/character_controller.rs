@@ -0,0 +1,177 @@
+use godot::global::godot_error;
+use rapier::control::CharacterAutostep;
+use rapier::control::CharacterCollision;
+use rapier::control::CharacterLength;
+use rapier::control::KinematicCharacterController;
+use rapier::prelude::*;
+
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsCollisionObjects;
+use crate::servers::rapier_physics_singleton::PhysicsIds;
+use crate::spaces::rapier_space::RapierSpace;
+pub struct CharacterMoveOptions {
+    pub up: Vector<Real>,
+    pub pixel_offset: Real,
+    pub slide: bool,
+    pub pixel_autostep_max_height: Option<Real>,
+    pub pixel_autostep_min_width: Real,
+    pub autostep_include_dynamic_bodies: bool,
+    pub max_slope_climb_angle: Real,
+    pub min_slope_slide_angle: Real,
+    pub pixel_snap_to_ground: Option<Real>,
+    pub normal_nudge_factor: Real,
+    pub apply_impulses: bool,
+    pub character_mass: Real,
+}
+pub struct CharacterCollisionInfo {
+    pub collider: ColliderHandle,
+    pub user_data: UserData,
+    pub pixel_position: Vector<Real>,
+    pub normal: Vector<Real>,
+    pub pixel_translation_applied: Vector<Real>,
+    pub pixel_translation_remaining: Vector<Real>,
+}
+#[derive(Default)]
+pub struct CharacterMoveResult {
+    pub pixel_translation: Vector<Real>,
+    pub grounded: bool,
+    pub is_sliding_down_slope: bool,
+    pub collisions: Vec<CharacterCollisionInfo>,
+}
+impl CharacterMoveOptions {
+    fn to_controller(&self) -> KinematicCharacterController {
+        KinematicCharacterController {
+            up: UnitVector::new_normalize(self.up),
+            offset: CharacterLength::Absolute(self.pixel_offset),
+            slide: self.slide,
+            autostep: self
+                .pixel_autostep_max_height
+                .map(|max_height| CharacterAutostep {
+                    max_height: CharacterLength::Absolute(max_height),
+                    min_width: CharacterLength::Absolute(self.pixel_autostep_min_width),
+                    include_dynamic_bodies: self.autostep_include_dynamic_bodies,
+                }),
+            max_slope_climb_angle: self.max_slope_climb_angle,
+            min_slope_slide_angle: self.min_slope_slide_angle,
+            snap_to_ground: self.pixel_snap_to_ground.map(CharacterLength::Absolute),
+            normal_nudge_factor: self.normal_nudge_factor,
+        }
+    }
+}
+impl PhysicsEngine {
+    // Shape moved by the character controller and its position. Several shapes are combined in a compound
+    // placed at the first shape. A compound can't hold composite shapes, so the parts of a compound are added
+    // one by one, and any other composite shape can't be moved.
+    fn get_character_shape(
+        &self,
+        shape_infos: &[ShapeInfo],
+    ) -> Option<(SharedShape, Isometry<Real>)> {
+        let first_shape_info = shape_infos.first()?;
+        if shape_infos.len() == 1 {
+            return Some((
+                self.get_scaled_shape(*first_shape_info)?,
+                first_shape_info.transform,
+            ));
+        }
+        let inverse_transform = first_shape_info.transform.inverse();
+        let mut shapes = Vec::new();
+        for shape_info in shape_infos {
+            let shape = self.get_scaled_shape(*shape_info)?;
+            let position = inverse_transform * shape_info.transform;
+            if let Some(compound) = shape.as_compound() {
+                for (sub_position, sub_shape) in compound.shapes() {
+                    shapes.push((position * sub_position, sub_shape.clone()));
+                }
+            } else if shape.as_composite_shape().is_some() {
+                return None;
+            } else {
+                shapes.push((position, shape));
+            }
+        }
+        Some((SharedShape::compound(shapes), first_shape_info.transform))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn character_move(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle: RigidBodyHandle,
+        shape_infos: &[ShapeInfo],
+        pixel_desired_translation: Vector<Real>,
+        options: &CharacterMoveOptions,
+        dt: Real,
+        handle_excluded_info: &QueryExcludedInfo,
+        physics_collision_objects: &PhysicsCollisionObjects,
+        physics_ids: &PhysicsIds,
+        space: &RapierSpace,
+    ) -> CharacterMoveResult {
+        let mut result = CharacterMoveResult::default();
+        let Some((shared_shape, shape_position)) = self.get_character_shape(shape_infos) else {
+            godot_error!("Character shapes can't be moved together, only convex shapes and compounds can be combined");
+            return result;
+        };
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return result;
+        };
+        let controller = options.to_controller();
+        let predicate = |handle: ColliderHandle, collider: &Collider| -> bool {
+            !space.is_handle_excluded_callback(
+                handle,
+                &UserData::new(collider.user_data),
+                handle_excluded_info,
+                physics_collision_objects,
+                physics_ids,
+            )
+        };
+        let mut filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_rigid_body(body_handle);
+        filter.predicate = Some(&predicate);
+        let physics_objects = &mut physics_world.physics_objects;
+        let mut collisions: Vec<CharacterCollision> = Vec::new();
+        let movement = controller.move_shape(
+            dt,
+            &physics_objects.rigid_body_set,
+            &physics_objects.collider_set,
+            &physics_objects.query_pipeline,
+            shared_shape.as_ref(),
+            &shape_position,
+            pixel_desired_translation,
+            filter,
+            |collision| collisions.push(collision),
+        );
+        if options.apply_impulses {
+            controller.solve_character_collision_impulses(
+                dt,
+                &mut physics_objects.rigid_body_set,
+                &physics_objects.collider_set,
+                &physics_objects.query_pipeline,
+                shared_shape.as_ref(),
+                options.character_mass,
+                &collisions,
+                filter,
+            );
+        }
+        result.pixel_translation = movement.translation;
+        result.grounded = movement.grounded;
+        result.is_sliding_down_slope = movement.is_sliding_down_slope;
+        for collision in &collisions {
+            let user_data = physics_objects
+                .collider_set
+                .get(collision.handle)
+                .map_or(UserData::invalid_user_data(), |collider| {
+                    UserData::new(collider.user_data)
+                });
+            // witness and normal 1 refer to the hit collider and are in world space
+            result.collisions.push(CharacterCollisionInfo {
+                collider: collision.handle,
+                user_data,
+                pixel_position: collision.hit.witness1.coords,
+                normal: collision.hit.normal1.into_inner(),
+                pixel_translation_applied: collision.translation_applied,
+                pixel_translation_remaining: collision.translation_remaining,
+            });
+        }
+        result
+    }
+}
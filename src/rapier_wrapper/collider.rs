@@ -8,6 +8,9 @@ use salva::parry::either::Either::Right;
 
 use crate::rapier_wrapper::prelude::*;
 const SUBDIVISIONS: u32 = 20;
+// Skewed versions kept per shape, enough for a shape shared by a few differently sheared instances.
+#[cfg(feature = "dim3")]
+const MAX_SKEWED_SHAPES: usize = 4;
 #[cfg(feature = "dim2")]
 fn skew_polyline(vertices: &Vec<Point<Real>>, skew: Real) -> SharedShape {
     // Apply skew transformation to the vertices
@@ -56,6 +59,15 @@ pub fn skew_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
                 return skew_polyline(&cuboid.to_polyline(), skew);
             }
         }
+        ShapeType::Segment => {
+            if let Some(segment) = shape.as_segment() {
+                let mut a = segment.a;
+                let mut b = segment.b;
+                a.x -= a.y * skew;
+                b.x -= b.y * skew;
+                return SharedShape::segment(a, b);
+            }
+        }
         ShapeType::Polyline => {
             if let Some(polyline) = shape.as_polyline() {
                 return skew_polyline(&polyline.vertices().to_vec(), skew);
@@ -78,9 +90,115 @@ pub fn skew_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
     shape.clone()
 }
 #[cfg(feature = "dim3")]
-fn skew_shape(shape: &SharedShape, _shape_info: ShapeInfo) -> SharedShape {
+fn transform_points(points: &[Point<Real>], matrix: &Matrix<Real>) -> Vec<Point<Real>> {
+    points
+        .iter()
+        .map(|point| Point::from(matrix * point.coords))
+        .collect()
+}
+// Applies a linear transformation to a shape. Primitives can't be skewed, so they become convex hulls.
+#[cfg(feature = "dim3")]
+fn transform_shape(shape: &SharedShape, matrix: &Matrix<Real>) -> Option<SharedShape> {
+    match shape.shape_type() {
+        ShapeType::Ball => {
+            let (vertices, _) = shape.as_ball()?.to_trimesh(SUBDIVISIONS, SUBDIVISIONS);
+            SharedShape::convex_hull(&transform_points(&vertices, matrix))
+        }
+        ShapeType::Cuboid => {
+            let (vertices, _) = shape.as_cuboid()?.to_trimesh();
+            SharedShape::convex_hull(&transform_points(&vertices, matrix))
+        }
+        ShapeType::Capsule => {
+            let (vertices, _) = shape.as_capsule()?.to_trimesh(SUBDIVISIONS, SUBDIVISIONS);
+            SharedShape::convex_hull(&transform_points(&vertices, matrix))
+        }
+        ShapeType::Cylinder => {
+            let (vertices, _) = shape.as_cylinder()?.to_trimesh(SUBDIVISIONS);
+            SharedShape::convex_hull(&transform_points(&vertices, matrix))
+        }
+        ShapeType::Cone => {
+            let (vertices, _) = shape.as_cone()?.to_trimesh(SUBDIVISIONS);
+            SharedShape::convex_hull(&transform_points(&vertices, matrix))
+        }
+        ShapeType::ConvexPolyhedron => {
+            let convex_polyhedron = shape.as_convex_polyhedron()?;
+            SharedShape::convex_hull(&transform_points(convex_polyhedron.points(), matrix))
+        }
+        ShapeType::Segment => {
+            let segment = shape.as_segment()?;
+            let points = transform_points(&[segment.a, segment.b], matrix);
+            Some(SharedShape::segment(points[0], points[1]))
+        }
+        ShapeType::Triangle => {
+            let triangle = shape.as_triangle()?;
+            let points = transform_points(triangle.vertices(), matrix);
+            Some(SharedShape::triangle(points[0], points[1], points[2]))
+        }
+        ShapeType::Polyline => {
+            let polyline = shape.as_polyline()?;
+            Some(SharedShape::polyline(
+                transform_points(polyline.vertices(), matrix),
+                Some(polyline.indices().to_vec()),
+            ))
+        }
+        ShapeType::TriMesh => {
+            let trimesh = shape.as_trimesh()?;
+            SharedShape::trimesh_with_flags(
+                transform_points(trimesh.vertices(), matrix),
+                trimesh.indices().to_vec(),
+                trimesh.flags(),
+            )
+            .ok()
+        }
+        ShapeType::HeightField => {
+            let (vertices, indices) = shape.as_heightfield()?.to_trimesh();
+            SharedShape::trimesh_with_flags(
+                transform_points(&vertices, matrix),
+                indices,
+                TriMeshFlags::FIX_INTERNAL_EDGES,
+            )
+            .ok()
+        }
+        ShapeType::HalfSpace => {
+            // Normals transform with the inverse transpose.
+            let normal =
+                matrix.try_inverse()?.transpose() * shape.as_halfspace()?.normal.into_inner();
+            Some(SharedShape::halfspace(UnitVector::try_new(
+                normal,
+                DEFAULT_EPSILON,
+            )?))
+        }
+        ShapeType::Compound => {
+            let mut shapes_vec = Vec::new();
+            for (position, sub_shape) in shape.as_compound()?.shapes() {
+                // Bake the rotation of the part in its shape, only the translation is kept.
+                let sub_matrix = matrix * position.rotation.to_rotation_matrix().matrix();
+                let new_position =
+                    Isometry::new(matrix * position.translation.vector, Vector::zeros());
+                shapes_vec.push((new_position, transform_shape(sub_shape, &sub_matrix)?));
+            }
+            Some(SharedShape::compound(shapes_vec))
+        }
+        _ => None,
+    }
+}
+#[cfg(feature = "dim3")]
+fn skew_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
+    if !is_skewed(&shape_info) {
+        return shape.clone();
+    }
+    if let Some(skewed_shape) = transform_shape(shape, &shape_info.skew) {
+        return skewed_shape;
+    }
+    godot_error!("Shape type not supported for skewing");
     shape.clone()
 }
+#[cfg(feature = "dim3")]
+fn is_skewed(shape_info: &ShapeInfo) -> bool {
+    !(shape_info.skew - Matrix::identity())
+        .norm_squared()
+        .is_zero_approx()
+}
 pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
     let shape = skew_shape(&shape.clone(), shape_info);
     let scale = shape_info.scale;
@@ -113,12 +231,21 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
                 }
             }
         }
+        ShapeType::Segment => {
+            if let Some(new_shape) = shape.as_segment() {
+                return SharedShape::new(new_shape.scaled(&scale));
+            }
+        }
+        ShapeType::Triangle => {
+            if let Some(new_shape) = shape.as_triangle() {
+                return SharedShape::new(new_shape.scaled(&scale));
+            }
+        }
         ShapeType::Polyline => {
             if let Some(new_shape) = shape.as_polyline() {
                 return SharedShape::new(new_shape.clone().scaled(&scale));
             }
         }
-        #[cfg(feature = "dim3")]
         ShapeType::TriMesh => {
             if let Some(new_shape) = shape.as_trimesh() {
                 return SharedShape::new(new_shape.clone().scaled(&scale));
@@ -152,6 +279,16 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
             }
         }
         #[cfg(feature = "dim3")]
+        ShapeType::Cone => {
+            if let Some(new_shape) = shape.as_cone() {
+                if let Some(new_shape) = new_shape.scaled(&scale, SUBDIVISIONS) {
+                    match new_shape {
+                        Left(shape) => return SharedShape::new(shape),
+                        Right(shape) => return SharedShape::new(shape),
+                    }
+                }
+            }
+        }
         ShapeType::HeightField => {
             if let Some(new_shape) = shape.as_heightfield() {
                 let new_shape = new_shape.clone().scaled(&scale);
@@ -170,11 +307,38 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
         }
         ShapeType::Compound => {
             if let Some(new_shape) = shape.as_compound() {
-                let new_shapes = new_shape.shapes();
+                // The compound is already skewed as a whole, only scale the parts.
+                let mut sub_shape_info = shape_info;
+                #[cfg(feature = "dim2")]
+                {
+                    sub_shape_info.skew = 0.0;
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    sub_shape_info.skew = Matrix::identity();
+                }
                 let mut shapes_vec = Vec::new();
-                for shape in new_shapes {
-                    let new_shape = scale_shape(&shape.1, shape_info);
-                    shapes_vec.push((shape.0, new_shape));
+                for (position, sub_shape) in new_shape.shapes() {
+                    let mut new_position = *position;
+                    new_position.translation.vector =
+                        position.translation.vector.component_mul(&scale);
+                    // A rotated part isn't aligned with the scale axes, bake its rotation in the shape.
+                    #[cfg(feature = "dim3")]
+                    if !position.rotation.angle().is_zero_approx()
+                        && !(scale - Vector::repeat(scale.x))
+                            .norm_squared()
+                            .is_zero_approx()
+                        && let Some(transformed_shape) = transform_shape(
+                            sub_shape,
+                            &(Matrix::from_diagonal(&scale)
+                                * position.rotation.to_rotation_matrix().matrix()),
+                        )
+                    {
+                        new_position.rotation = Rotation::identity();
+                        shapes_vec.push((new_position, transformed_shape));
+                        continue;
+                    }
+                    shapes_vec.push((new_position, scale_shape(sub_shape, sub_shape_info)));
                 }
                 return SharedShape::compound(shapes_vec);
             }
@@ -185,9 +349,12 @@ pub fn scale_shape(shape: &SharedShape, shape_info: ShapeInfo) -> SharedShape {
     }
     shape.clone()
 }
+#[derive(Clone, Copy)]
 pub struct Material {
     pub friction: Option<Real>,
     pub restitution: Option<Real>,
+    pub friction_combine_rule: Option<CoefficientCombineRule>,
+    pub restitution_combine_rule: Option<CoefficientCombineRule>,
     pub contact_skin: Option<Real>,
     pub collision_mask: Option<u32>,
     pub collision_layer: Option<u32>,
@@ -197,12 +364,39 @@ impl Material {
         Material {
             friction: None,
             restitution: None,
+            friction_combine_rule: None,
+            restitution_combine_rule: None,
             contact_skin: None,
             collision_layer: Some(collision_layer),
             collision_mask: Some(collision_mask),
         }
     }
 }
+pub fn apply_material(collider: &mut Collider, mat: &Material) {
+    if let Some(friction) = mat.friction {
+        collider.set_friction(friction);
+    }
+    if let Some(restitution) = mat.restitution {
+        collider.set_restitution(restitution);
+    }
+    if let Some(friction_combine_rule) = mat.friction_combine_rule {
+        collider.set_friction_combine_rule(friction_combine_rule);
+    }
+    if let Some(restitution_combine_rule) = mat.restitution_combine_rule {
+        collider.set_restitution_combine_rule(restitution_combine_rule);
+    }
+    if let Some(contact_skin) = mat.contact_skin {
+        collider.set_contact_skin(contact_skin);
+    }
+    if let Some(collision_mask) = mat.collision_mask
+        && let Some(collision_layer) = mat.collision_layer
+    {
+        collider.set_collision_groups(InteractionGroups {
+            memberships: Group::from(collision_layer),
+            filter: Group::from(collision_mask),
+        });
+    }
+}
 fn shape_is_halfspace(shape: &SharedShape) -> bool {
     if shape.shape_type() == ShapeType::Compound {
         if let Some(shape) = shape.as_compound() {
@@ -216,6 +410,53 @@ fn shape_is_halfspace(shape: &SharedShape) -> bool {
     shape.shape_type() == ShapeType::HalfSpace
 }
 impl PhysicsEngine {
+    // Shape of the shape info, skewed and scaled. The skewed shape is reused while the skew doesn't change.
+    pub fn get_scaled_shape(&self, shape_info: ShapeInfo) -> Option<SharedShape> {
+        let shape = self.get_shape(shape_info.handle)?;
+        #[cfg(feature = "dim3")]
+        if is_skewed(&shape_info) {
+            let mut skewed_shapes = self.skewed_shapes.borrow_mut();
+            let skewed_shapes = skewed_shapes.entry(shape_info.handle).or_default();
+            let cached = skewed_shapes
+                .iter()
+                .position(|(skew, _)| (skew - shape_info.skew).norm_squared() <= DEFAULT_EPSILON);
+            let skewed_shape = match cached {
+                Some(index) => {
+                    let entry = skewed_shapes.remove(index);
+                    let skewed_shape = entry.1.clone();
+                    skewed_shapes.insert(0, entry);
+                    skewed_shape
+                }
+                None => {
+                    let skewed_shape = skew_shape(shape, shape_info);
+                    skewed_shapes.insert(0, (shape_info.skew, skewed_shape.clone()));
+                    skewed_shapes.truncate(MAX_SKEWED_SHAPES);
+                    skewed_shape
+                }
+            };
+            let mut unskewed_shape_info = shape_info;
+            unskewed_shape_info.skew = Matrix::identity();
+            return Some(scale_shape(&skewed_shape, unskewed_shape_info));
+        }
+        Some(scale_shape(shape, shape_info))
+    }
+
+    pub fn collider_update_material(
+        &mut self,
+        world_handle: WorldHandle,
+        collider_handle: ColliderHandle,
+        mat: &Material,
+    ) {
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(collider) = physics_world
+                .physics_objects
+                .collider_set
+                .get_mut(collider_handle)
+        {
+            apply_material(collider, mat);
+        }
+    }
+
     pub fn collider_set_modify_contacts_enabled(
         &mut self,
         world_handle: WorldHandle,
@@ -282,8 +523,14 @@ impl PhysicsEngine {
             if let Some(restitution) = mat.restitution {
                 collider.set_restitution(restitution);
             }
-            collider.set_friction_combine_rule(CoefficientCombineRule::Min);
-            collider.set_restitution_combine_rule(CoefficientCombineRule::Sum);
+            collider.set_friction_combine_rule(
+                mat.friction_combine_rule
+                    .unwrap_or(CoefficientCombineRule::Min),
+            );
+            collider.set_restitution_combine_rule(
+                mat.restitution_combine_rule
+                    .unwrap_or(CoefficientCombineRule::Sum),
+            );
             if let Some(collision_mask) = mat.collision_mask
                 && let Some(collision_layer) = mat.collision_layer
             {
@@ -403,8 +650,7 @@ impl PhysicsEngine {
         collider_handle: ColliderHandle,
         shape_info: ShapeInfo,
     ) {
-        if let Some(shape) = self.get_shape(shape_info.handle) {
-            let new_shape = scale_shape(shape, shape_info);
+        if let Some(new_shape) = self.get_scaled_shape(shape_info) {
             if let Some(physics_world) = self.get_mut_world(world_handle) {
                 if let Some(collider) = physics_world
                     .physics_objects
@@ -34,3 +34,15 @@ pub fn angle_to_rapier(angle: Angle) -> Real {
 pub fn angle_to_godot(angle: Real) -> Angle {
     angle
 }
+#[cfg(feature = "dim3")]
+pub fn transform_to_rapier(transform: crate::Transform3D) -> Isometry<Real> {
+    let transform = crate::Transform3D::new(transform.basis.orthonormalized(), transform.origin);
+    Isometry::from_parts(
+        Translation::from(vector_to_rapier(transform.origin)),
+        transform_rotation_rapier(&transform),
+    )
+}
+#[cfg(feature = "dim2")]
+pub fn transform_to_rapier(transform: crate::Transform2D) -> Isometry<Real> {
+    Isometry::new(vector_to_rapier(transform.origin), transform.rotation())
+}
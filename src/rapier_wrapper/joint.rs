@@ -2,22 +2,16 @@ use rapier::prelude::*;
 
 use crate::rapier_wrapper::prelude::*;
 impl PhysicsEngine {
-    fn joint_wake_up_connected_rigidbodies(
+    pub fn joint_wake_up_connected_rigidbodies(
         &mut self,
         world_handle: WorldHandle,
         joint_handle: JointHandle,
     ) {
-        let mut body1 = None;
-        let mut body2 = None;
-        if let Some(physics_world) = self.get_world(world_handle)
-            && let Some(joint) = physics_world.get_impulse_joint(joint_handle)
-        {
-            body1 = Some(joint.body1);
-            body2 = Some(joint.body2);
+        let mut bodies = None;
+        if let Some(physics_world) = self.get_world(world_handle) {
+            bodies = physics_world.get_joint_bodies(joint_handle);
         }
-        if let Some(body1) = body1
-            && let Some(body2) = body2
-        {
+        if let Some((body1, body2)) = bodies {
             self.body_wake_up(world_handle, body1, false);
             self.body_wake_up(world_handle, body2, false);
         }
@@ -140,6 +134,135 @@ impl PhysicsEngine {
         JointHandle::default()
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_create_fixed(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        frame_1: Isometry<Real>,
+        frame_2: Isometry<Real>,
+        multibody: bool,
+        kinematic: bool,
+        disable_collision: bool,
+    ) -> JointHandle {
+        self.body_wake_up(world_handle, body_handle_1, false);
+        self.body_wake_up(world_handle, body_handle_2, false);
+        if let Some(physics_world) = self.get_mut_world(world_handle) {
+            let joint = FixedJointBuilder::new()
+                .local_frame1(frame_1)
+                .local_frame2(frame_2)
+                .contacts_enabled(!disable_collision);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                multibody,
+                kinematic,
+                joint,
+            );
+        }
+        JointHandle::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_create_rope(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        anchor_1: Vector<Real>,
+        anchor_2: Vector<Real>,
+        min_length: Real,
+        max_length: Real,
+        multibody: bool,
+        kinematic: bool,
+        disable_collision: bool,
+    ) -> JointHandle {
+        self.body_wake_up(world_handle, body_handle_1, false);
+        self.body_wake_up(world_handle, body_handle_2, false);
+        if let Some(physics_world) = self.get_mut_world(world_handle) {
+            let mut joint = RopeJointBuilder::new(max_length)
+                .local_anchor1(Point { coords: anchor_1 })
+                .local_anchor2(Point { coords: anchor_2 })
+                .contacts_enabled(!disable_collision)
+                .build();
+            joint
+                .data
+                .set_limits(JointAxis::LinX, [min_length, max_length]);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                multibody,
+                kinematic,
+                joint,
+            );
+        }
+        JointHandle::default()
+    }
+
+    pub fn joint_change_rope_params(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        min_length: Real,
+        max_length: Real,
+        compliance: Real,
+    ) {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        if let Some(physics_world) = self.get_mut_world(world_handle)
+            && let Some(joint) = physics_world.get_mut_joint(joint_handle)
+        {
+            if compliance > 0.0 {
+                // The max length is held by a spring, see joint_update_rope_spring.
+                joint.set_limits(JointAxis::LinX, [min_length, Real::MAX]);
+            } else {
+                joint.set_limits(JointAxis::LinX, [min_length, max_length]);
+                joint.set_motor(JointAxis::LinX, 0.0, 0.0, 0.0, 0.0);
+                joint.motor_axes.remove(JointAxesMask::LIN_X);
+            }
+        }
+    }
+
+    // Rapier motors pull both ways, so the spring of a compliant rope is only
+    // enabled while the rope is stretched past its max length.
+    pub fn joint_update_rope_spring(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        max_length: Real,
+        compliance: Real,
+    ) {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return;
+        };
+        let Some((body_handle_1, body_handle_2)) = physics_world.get_joint_bodies(joint_handle)
+        else {
+            return;
+        };
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let (Some(body_1), Some(body_2), Some(joint)) = (
+            rigid_body_set.get(body_handle_1),
+            rigid_body_set.get(body_handle_2),
+            physics_world.get_joint(joint_handle),
+        ) else {
+            return;
+        };
+        let anchor_1 = body_1.position() * joint.local_anchor1();
+        let anchor_2 = body_2.position() * joint.local_anchor2();
+        let stretched = (anchor_2 - anchor_1).norm() > max_length;
+        let Some(joint) = physics_world.get_mut_joint(joint_handle) else {
+            return;
+        };
+        if stretched {
+            joint
+                .set_motor(JointAxis::LinX, max_length, 0.0, 1.0 / compliance, 0.0)
+                .set_motor_model(JointAxis::LinX, MotorModel::ForceBased);
+        } else {
+            joint.set_motor(JointAxis::LinX, 0.0, 0.0, 0.0, 0.0);
+            joint.motor_axes.remove(JointAxesMask::LIN_X);
+        }
+    }
+
     #[cfg(feature = "dim3")]
     #[allow(clippy::too_many_arguments)]
     pub fn joint_create_slider(
@@ -177,19 +300,67 @@ impl PhysicsEngine {
     }
 
     #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
     pub fn joint_change_slider(
         &mut self,
         world_handle: WorldHandle,
         joint_handle: JointHandle,
         linear_limit_lower: Real,
         linear_limit_upper: Real,
+        angular_limit_lower: Real,
+        angular_limit_upper: Real,
+    ) -> JointHandle {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return joint_handle;
+        };
+        physics_world.modify_joint(joint_handle, |joint| {
+            // lower > upper means the axis is free.
+            if linear_limit_lower > linear_limit_upper {
+                joint.limit_axes.remove(JointAxesMask::LIN_X);
+            } else {
+                joint.set_limits(JointAxis::LinX, [linear_limit_lower, linear_limit_upper]);
+            }
+            // The slider can only rotate around its axis if it has an angular range.
+            if angular_limit_lower > angular_limit_upper {
+                joint.locked_axes.remove(JointAxesMask::ANG_X);
+                joint.limit_axes.remove(JointAxesMask::ANG_X);
+            } else if angular_limit_lower == 0.0 && angular_limit_upper == 0.0 {
+                joint.locked_axes.insert(JointAxesMask::ANG_X);
+                joint.limit_axes.remove(JointAxesMask::ANG_X);
+            } else {
+                joint.locked_axes.remove(JointAxesMask::ANG_X);
+                joint.set_limits(JointAxis::AngX, [angular_limit_lower, angular_limit_upper]);
+            }
+        })
+    }
+
+    #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_change_slider_motor(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        axis: JointAxis,
+        enabled: bool,
+        target_velocity: Real,
+        target_position: Real,
+        stiffness: Real,
+        damping: Real,
+        max_force: Real,
     ) {
         self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
         if let Some(physics_world) = self.get_mut_world(world_handle)
             && let Some(joint) = physics_world.get_mut_joint(joint_handle)
-            && let Some(joint) = joint.as_prismatic_mut()
         {
-            joint.set_limits([linear_limit_lower, linear_limit_upper]);
+            if enabled {
+                joint
+                    .set_motor(axis, target_position, target_velocity, stiffness, damping)
+                    .set_motor_max_force(axis, max_force);
+            } else {
+                joint.set_motor(axis, 0.0, 0.0, 0.0, 0.0);
+                joint.motor_axes.remove(JointAxesMask::from(axis));
+            }
         }
     }
 
@@ -212,6 +383,203 @@ impl PhysicsEngine {
         }
     }
 
+    #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_create_generic_6dof(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        frame_1: Isometry<Real>,
+        frame_2: Isometry<Real>,
+        multibody: bool,
+        kinematic: bool,
+        disable_collision: bool,
+    ) -> JointHandle {
+        self.body_wake_up(world_handle, body_handle_1, false);
+        self.body_wake_up(world_handle, body_handle_2, false);
+        if let Some(physics_world) = self.get_mut_world(world_handle) {
+            let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_FIXED_AXES)
+                .local_frame1(frame_1)
+                .local_frame2(frame_2)
+                .contacts_enabled(!disable_collision);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                multibody,
+                kinematic,
+                joint,
+            );
+        }
+        JointHandle::default()
+    }
+
+    #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_change_generic_6dof_axis(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        axis: JointAxis,
+        limit_enabled: bool,
+        limit_lower: Real,
+        limit_upper: Real,
+        motor_enabled: bool,
+        motor_target_velocity: Real,
+        motor_max_force: Real,
+        spring_enabled: bool,
+        spring_stiffness: Real,
+        spring_damping: Real,
+        spring_equilibrium: Real,
+    ) -> JointHandle {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return joint_handle;
+        };
+        physics_world.modify_joint(joint_handle, |joint| {
+            let axis_mask: JointAxesMask = axis.into();
+            // lower > upper means the axis is free, lower == upper == 0 means it is locked.
+            if limit_enabled && limit_lower <= limit_upper {
+                if limit_lower == 0.0 && limit_upper == 0.0 {
+                    joint.locked_axes.insert(axis_mask);
+                    joint.limit_axes.remove(axis_mask);
+                } else {
+                    joint.locked_axes.remove(axis_mask);
+                    joint.set_limits(axis, [limit_lower, limit_upper]);
+                }
+            } else {
+                joint.locked_axes.remove(axis_mask);
+                joint.limit_axes.remove(axis_mask);
+            }
+            // Both the spring and the motor drive the single motor of the axis. The spring is
+            // force based like in Godot, and when both are enabled it overrides the motor
+            // target velocity and force limit.
+            if spring_enabled {
+                joint
+                    .set_motor(
+                        axis,
+                        spring_equilibrium,
+                        0.0,
+                        spring_stiffness,
+                        spring_damping,
+                    )
+                    .set_motor_max_force(axis, Real::MAX)
+                    .set_motor_model(axis, MotorModel::ForceBased);
+            } else if motor_enabled {
+                joint
+                    .set_motor(axis, 0.0, motor_target_velocity, 0.0, 0.0)
+                    .set_motor_max_force(axis, motor_max_force)
+                    .set_motor_model(axis, MotorModel::AccelerationBased);
+            } else {
+                joint
+                    .set_motor(axis, 0.0, 0.0, 0.0, 0.0)
+                    .set_motor_max_force(axis, 0.0);
+                joint.motor_axes.remove(axis_mask);
+            }
+        })
+    }
+
+    #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_create_cone_twist(
+        &mut self,
+        world_handle: WorldHandle,
+        body_handle_1: RigidBodyHandle,
+        body_handle_2: RigidBodyHandle,
+        frame_1: Isometry<Real>,
+        frame_2: Isometry<Real>,
+        multibody: bool,
+        kinematic: bool,
+        disable_collision: bool,
+    ) -> JointHandle {
+        self.body_wake_up(world_handle, body_handle_1, false);
+        self.body_wake_up(world_handle, body_handle_2, false);
+        if let Some(physics_world) = self.get_mut_world(world_handle) {
+            // The swing and twist limits are applied before each step, see
+            // joint_update_cone_twist_limits.
+            let joint = GenericJointBuilder::new(JointAxesMask::LOCKED_SPHERICAL_AXES)
+                .local_frame1(frame_1)
+                .local_frame2(frame_2)
+                .contacts_enabled(!disable_collision);
+            return physics_world.insert_joint(
+                body_handle_1,
+                body_handle_2,
+                multibody,
+                kinematic,
+                joint,
+            );
+        }
+        JointHandle::default()
+    }
+
+    // Rapier limits are hard limits and motors pull both ways, so the swing and twist
+    // limits are springs that are only enabled while the joint is past them. Twist happens
+    // around the X axis of the joint frame, swing is the angle between the X axes of both
+    // frames, which forms a cone. Spans of PI or more leave the axes free.
+    #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_update_cone_twist_limits(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        swing_span: Real,
+        twist_span: Real,
+        stiffness: Real,
+        damping: Real,
+    ) {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return;
+        };
+        let Some((body_handle_1, body_handle_2)) = physics_world.get_joint_bodies(joint_handle)
+        else {
+            return;
+        };
+        let rigid_body_set = &physics_world.physics_objects.rigid_body_set;
+        let (Some(body_1), Some(body_2), Some(joint)) = (
+            rigid_body_set.get(body_handle_1),
+            rigid_body_set.get(body_handle_2),
+            physics_world.get_joint(joint_handle),
+        ) else {
+            return;
+        };
+        let frame_1 = body_1.position() * joint.local_frame1;
+        let frame_2 = body_2.position() * joint.local_frame2;
+        let mut rotation = frame_1.rotation.inverse() * frame_2.rotation;
+        if rotation.w < 0.0 {
+            rotation = Rotation::new_unchecked(-rotation.into_inner());
+        }
+        // Same angles the solver uses for the motors of each axis.
+        let angles = rotation.imag().map(|s| 2.0 * s.clamp(-1.0, 1.0).asin());
+        let swing = (rotation * Vector::x()).x.clamp(-1.0, 1.0).acos();
+        let free_span = std::f64::consts::PI as Real;
+        let mut targets = [None; 3];
+        if twist_span < free_span && angles.x.abs() > twist_span.max(0.0) {
+            targets[0] = Some(angles.x.clamp(-twist_span.max(0.0), twist_span.max(0.0)));
+        }
+        if swing_span < free_span && swing > swing_span.max(0.0) {
+            // Pull both swing axes back onto the cone.
+            let scale = swing_span.max(0.0) / swing;
+            targets[1] = Some(angles.y * scale);
+            targets[2] = Some(angles.z * scale);
+        }
+        let Some(joint) = physics_world.get_mut_joint(joint_handle) else {
+            return;
+        };
+        let axes = [JointAxis::AngX, JointAxis::AngY, JointAxis::AngZ];
+        for (axis, target) in axes.into_iter().zip(targets) {
+            if let Some(target) = target
+                && stiffness > 0.0
+            {
+                joint
+                    .set_motor(axis, target, 0.0, stiffness, damping)
+                    .set_motor_model(axis, MotorModel::AccelerationBased);
+            } else {
+                joint.set_motor(axis, 0.0, 0.0, 0.0, 0.0);
+                joint.motor_axes.remove(axis.into());
+            }
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn joint_change_revolute_params(
         &mut self,
@@ -334,6 +702,143 @@ impl PhysicsEngine {
         }
     }
 
+    // Moves the joint between the impulse joint set and the multibody joint set, keeping its data.
+    // Returns the new handle, or the old one if the body is already part of another articulation.
+    pub fn joint_change_multibody(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        multibody: bool,
+    ) -> JointHandle {
+        if joint_handle.multibody == multibody {
+            return joint_handle;
+        }
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return joint_handle;
+        };
+        let Some((body_1, body_2)) = physics_world.get_joint_bodies(joint_handle) else {
+            return joint_handle;
+        };
+        let Some(joint) = physics_world.get_joint(joint_handle).cloned() else {
+            return joint_handle;
+        };
+        physics_world.remove_joint(joint_handle);
+        let new_joint_handle =
+            physics_world.insert_joint(body_1, body_2, multibody, joint_handle.kinematic, joint);
+        if new_joint_handle != JointHandle::default() {
+            return new_joint_handle;
+        }
+        physics_world.insert_joint(
+            body_1,
+            body_2,
+            joint_handle.multibody,
+            joint_handle.kinematic,
+            joint,
+        )
+    }
+
+    // Removes the impulse joints and inserts them back in the given order, so that the solver
+    // goes through them in that order. The impulses are kept, so warmstarting isn't lost.
+    // Returns the new handles, in the same order.
+    pub fn joint_reinsert_in_order(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handles: &[JointHandle],
+    ) -> Vec<JointHandle> {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return joint_handles.to_vec();
+        };
+        let joints: Vec<Option<ImpulseJoint>> = joint_handles
+            .iter()
+            .map(|joint_handle| physics_world.get_impulse_joint(*joint_handle).cloned())
+            .collect();
+        for (joint_handle, joint) in joint_handles.iter().zip(&joints) {
+            if joint.is_some() {
+                physics_world.remove_joint(*joint_handle);
+            }
+        }
+        let mut new_joint_handles = Vec::with_capacity(joint_handles.len());
+        for (joint_handle, joint) in joint_handles.iter().zip(joints) {
+            let Some(joint) = joint else {
+                new_joint_handles.push(*joint_handle);
+                continue;
+            };
+            let new_joint_handle = physics_world.insert_joint(
+                joint.body1,
+                joint.body2,
+                false,
+                joint_handle.kinematic,
+                joint.data,
+            );
+            if let Some(new_joint) = physics_world
+                .physics_objects
+                .impulse_joint_set
+                .get_mut(ImpulseJointHandle(new_joint_handle.index), false)
+            {
+                new_joint.impulses = joint.impulses;
+            }
+            new_joint_handles.push(new_joint_handle);
+        }
+        new_joint_handles
+    }
+
+    // Rapier only has a global joint softness. To soften a single joint, its locked axes
+    // are replaced by acceleration based motors holding them at their rest position.
+    // The managed axes are locked, limited or motorized by the joint type itself and are left untouched.
+    // Returns the handle of the joint and the axes that were softened, to be passed back on the next change.
+    #[allow(clippy::too_many_arguments)]
+    pub fn joint_change_softness(
+        &mut self,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        softened_axes: u8,
+        managed_axes: u8,
+        soft: bool,
+        stiffness: Real,
+        damping: Real,
+        max_force: Real,
+    ) -> (JointHandle, u8) {
+        self.joint_wake_up_connected_rigidbodies(world_handle, joint_handle);
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return (joint_handle, softened_axes);
+        };
+        let mut new_softened_axes = softened_axes;
+        let joint_handle = physics_world.modify_joint(joint_handle, |joint| {
+            let managed_axes = JointAxesMask::from_bits_truncate(managed_axes);
+            let softened_axes = JointAxesMask::from_bits_truncate(softened_axes) - managed_axes;
+            joint.locked_axes.insert(softened_axes);
+            joint.motor_axes.remove(softened_axes);
+            new_softened_axes = 0;
+            // A spring without stiffness would let the bodies drift apart, keep the axes locked instead.
+            if !soft || stiffness <= 0.0 {
+                return;
+            }
+            let softened_axes = joint.locked_axes - managed_axes;
+            joint.locked_axes.remove(softened_axes);
+            for axis in [
+                JointAxis::LinX,
+                JointAxis::LinY,
+                #[cfg(feature = "dim3")]
+                JointAxis::LinZ,
+                JointAxis::AngX,
+                #[cfg(feature = "dim3")]
+                JointAxis::AngY,
+                #[cfg(feature = "dim3")]
+                JointAxis::AngZ,
+            ] {
+                if !softened_axes.contains(JointAxesMask::from(axis)) {
+                    continue;
+                }
+                joint
+                    .set_motor(axis, 0.0, 0.0, stiffness, damping)
+                    .set_motor_max_force(axis, max_force)
+                    .set_motor_model(axis, MotorModel::AccelerationBased);
+            }
+            new_softened_axes = softened_axes.bits();
+        });
+        (joint_handle, new_softened_axes)
+    }
+
     pub fn joint_change_disable_collision(
         &mut self,
         world_handle: WorldHandle,
@@ -347,4 +852,233 @@ impl PhysicsEngine {
             joint.set_contacts_enabled(!disable_collision);
         }
     }
+
+    // Disables the joints whose impulse during the last step went over their max impulse and
+    // returns them. Godot only has a max force, so it bounds both the linear and the angular
+    // impulse. Disabled joints are skipped, so a broken joint is only returned once.
+    // Multibody joints don't expose their impulses, so they never break.
+    pub fn joints_break(
+        &mut self,
+        world_handle: WorldHandle,
+        joints: &[(JointHandle, Real)],
+    ) -> Vec<JointHandle> {
+        let mut broken_joints = Vec::default();
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return broken_joints;
+        };
+        for (joint_handle, max_impulse) in joints {
+            let Some(joint) = physics_world.get_impulse_joint(*joint_handle) else {
+                continue;
+            };
+            if !joint.data.is_enabled() {
+                continue;
+            }
+            let linear_impulse = joint.impulses.fixed_rows::<DIM>(0).norm();
+            let angular_impulse = joint.impulses.fixed_rows::<ANG_DIM>(DIM).norm();
+            if linear_impulse.max(angular_impulse) > *max_impulse {
+                broken_joints.push(*joint_handle);
+            }
+        }
+        for joint_handle in &broken_joints {
+            if let Some(joint) = physics_world.get_mut_joint(*joint_handle) {
+                joint.set_enabled(false);
+            }
+        }
+        broken_joints
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn create_joint(
+        physics_engine: &mut PhysicsEngine,
+        world_handle: WorldHandle,
+        multibody: bool,
+    ) -> JointHandle {
+        physics_engine.world_create(
+            &WorldSettings {
+                particle_radius: 1.0,
+                smoothing_factor: 1.0,
+                counters_enabled: false,
+            },
+            world_handle,
+        );
+        let mut bodies = Vec::default();
+        for _ in 0..2 {
+            bodies.push(physics_engine.body_create(
+                world_handle,
+                Vector::zeros(),
+                Rotation::identity(),
+                BodyType::Dynamic,
+                0.0,
+                0.0,
+                0.0,
+                0,
+            ));
+        }
+        physics_engine.joint_create_fixed(
+            world_handle,
+            bodies[0],
+            bodies[1],
+            Isometry::identity(),
+            Isometry::identity(),
+            multibody,
+            false,
+            true,
+        )
+    }
+    fn set_impulses(
+        physics_engine: &mut PhysicsEngine,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+        linear: Real,
+        angular: Real,
+    ) {
+        let physics_world = physics_engine.get_mut_world(world_handle).unwrap();
+        let joint = physics_world
+            .physics_objects
+            .impulse_joint_set
+            .get_mut(ImpulseJointHandle(joint_handle.index), false)
+            .unwrap();
+        joint.impulses = SpatialVector::zeros();
+        joint.impulses[0] = linear;
+        joint.impulses[DIM] = angular;
+    }
+    fn is_enabled(
+        physics_engine: &PhysicsEngine,
+        world_handle: WorldHandle,
+        joint_handle: JointHandle,
+    ) -> bool {
+        physics_engine
+            .get_world(world_handle)
+            .and_then(|physics_world| physics_world.get_joint(joint_handle))
+            .is_some_and(|joint| joint.is_enabled())
+    }
+    #[test]
+    fn test_joints_break_over_max_impulse_once() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let joint_handle = create_joint(&mut physics_engine, world_handle, false);
+        let max_force = 10.0;
+        let step = 1.0 / 60.0;
+        let joints = [(joint_handle, max_force * step)];
+        set_impulses(
+            &mut physics_engine,
+            world_handle,
+            joint_handle,
+            0.5 * max_force * step,
+            0.0,
+        );
+        assert!(physics_engine
+            .joints_break(world_handle, &joints)
+            .is_empty());
+        assert!(is_enabled(&physics_engine, world_handle, joint_handle));
+        set_impulses(
+            &mut physics_engine,
+            world_handle,
+            joint_handle,
+            2.0 * max_force * step,
+            0.0,
+        );
+        assert_eq!(
+            physics_engine.joints_break(world_handle, &joints),
+            vec![joint_handle]
+        );
+        assert!(!is_enabled(&physics_engine, world_handle, joint_handle));
+        // The impulses of a disabled joint are stale, it must not be reported again.
+        assert!(physics_engine
+            .joints_break(world_handle, &joints)
+            .is_empty());
+    }
+    #[test]
+    fn test_joints_break_over_max_angular_impulse() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let joint_handle = create_joint(&mut physics_engine, world_handle, false);
+        let joints = [(joint_handle, 1.0)];
+        set_impulses(&mut physics_engine, world_handle, joint_handle, 0.0, 2.0);
+        assert_eq!(
+            physics_engine.joints_break(world_handle, &joints),
+            vec![joint_handle]
+        );
+        assert!(!is_enabled(&physics_engine, world_handle, joint_handle));
+    }
+    #[test]
+    fn test_softness_reinserts_multibody_joint() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let joint_handle = create_joint(&mut physics_engine, world_handle, true);
+        assert!(joint_handle.multibody);
+        let (joint_handle, softened_axes) = physics_engine.joint_change_softness(
+            world_handle,
+            joint_handle,
+            0,
+            0,
+            true,
+            100.0,
+            10.0,
+            Real::MAX,
+        );
+        assert!(joint_handle.multibody);
+        assert_eq!(softened_axes, JointAxesMask::LOCKED_FIXED_AXES.bits());
+        let physics_world = physics_engine.get_world(world_handle).unwrap();
+        let (multibody, link_id) = physics_world
+            .physics_objects
+            .multibody_joint_set
+            .get(MultibodyJointHandle(joint_handle.index))
+            .unwrap();
+        let link_joint = &multibody.link(link_id).unwrap().joint;
+        // The link was inserted again, so its degrees of freedom follow the unlocked axes.
+        assert!(link_joint.data.locked_axes.is_empty());
+        assert_eq!(link_joint.ndofs(), SPATIAL_DIM);
+    }
+    #[test]
+    fn test_joint_reinsert_in_order_keeps_impulses() {
+        let mut physics_engine = PhysicsEngine::default();
+        let world_handle = 1;
+        let first_joint = create_joint(&mut physics_engine, world_handle, false);
+        let (body_1, body_2) = physics_engine
+            .get_world(world_handle)
+            .and_then(|physics_world| physics_world.get_joint_bodies(first_joint))
+            .unwrap();
+        let mut joint_handles = vec![first_joint];
+        for _ in 0..2 {
+            joint_handles.push(physics_engine.joint_create_fixed(
+                world_handle,
+                body_1,
+                body_2,
+                Isometry::identity(),
+                Isometry::identity(),
+                false,
+                false,
+                true,
+            ));
+        }
+        for (i, joint_handle) in joint_handles.iter().enumerate() {
+            set_impulses(
+                &mut physics_engine,
+                world_handle,
+                *joint_handle,
+                i as Real,
+                0.0,
+            );
+        }
+        joint_handles.reverse();
+        let new_joint_handles =
+            physics_engine.joint_reinsert_in_order(world_handle, &joint_handles);
+        let physics_world = physics_engine.get_world(world_handle).unwrap();
+        // The solver goes through the joints in the order of the joint graph.
+        let order: Vec<Real> = physics_world
+            .physics_objects
+            .impulse_joint_set
+            .joint_graph()
+            .interactions()
+            .map(|joint| joint.impulses[0])
+            .collect();
+        assert_eq!(order, vec![2.0, 1.0, 0.0]);
+        for (i, joint_handle) in new_joint_handles.iter().enumerate() {
+            let joint = physics_world.get_impulse_joint(*joint_handle).unwrap();
+            assert_eq!(joint.impulses[0], (2 - i) as Real);
+        }
+    }
 }
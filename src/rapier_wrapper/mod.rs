@@ -1,4 +1,5 @@
 pub mod body;
+pub mod character_controller;
 pub mod collider;
 pub mod convert;
 pub mod event_handler;
@@ -12,6 +13,8 @@ pub mod query;
 pub mod settings;
 pub mod shape;
 pub mod user_data;
+#[cfg(feature = "dim3")]
+pub mod vehicle;
 #[cfg(feature = "dim2")]
 pub const ANG_ZERO: rapier::math::Real = 0.0;
 #[cfg(feature = "dim3")]
@@ -11,6 +11,8 @@ pub struct OneWayDirection {
     pub pixel_body2_margin: Real,
     pub previous_linear_velocity1: Vector<Real>,
     pub previous_linear_velocity2: Vector<Real>,
+    pub collision_priority1: Real,
+    pub collision_priority2: Real,
 }
 pub type CollisionFilterCallback = fn(
     filter_info: &CollisionFilterInfo,
@@ -87,6 +89,35 @@ pub fn update_as_oneway_platform(
         _ => unreachable!(),
     }
 }
+// Rapier splits the penetration recovery of a contact between the two bodies by their inverse mass.
+// Godot pushes each body by the priority of the other one instead, p2 / (p1 + p2) for the first body,
+// so a body with a higher priority is pushed less. The split itself can't be changed, so the recovery
+// is scaled down until neither body is pushed more than its share.
+pub fn collision_priority_recovery_scale(
+    body1: &RigidBody,
+    body2: &RigidBody,
+    priority1: Real,
+    priority2: Real,
+) -> Real {
+    let inv_mass1 = body1.mass_properties().local_mprops.inv_mass;
+    let inv_mass2 = body2.mass_properties().local_mprops.inv_mass;
+    let inv_mass_sum = inv_mass1 + inv_mass2;
+    let priority_sum = priority1 + priority2;
+    if inv_mass_sum <= 0.0 || priority_sum <= 0.0 {
+        return 1.0;
+    }
+    let mut scale: Real = 1.0;
+    for (inv_mass, priority_share) in [
+        (inv_mass1, priority2 / priority_sum),
+        (inv_mass2, priority1 / priority_sum),
+    ] {
+        let mass_share = inv_mass / inv_mass_sum;
+        if mass_share > priority_share {
+            scale = scale.min(priority_share / mass_share);
+        }
+    }
+    scale
+}
 impl<'a> PhysicsHooks for PhysicsHooksCollisionFilter<'a> {
     fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
         let result = Some(SolverFlags::COMPUTE_IMPULSES);
@@ -160,6 +191,25 @@ impl<'a> PhysicsHooks for PhysicsHooksCollisionFilter<'a> {
         if rigid_body_2_linvel.norm() == 0.0 {
             rigid_body_2_linvel = *body2.linvel();
         }
+        // collision priority
+        if body1.is_dynamic()
+            && body2.is_dynamic()
+            && one_way_direction.collision_priority1 > 0.0
+            && one_way_direction.collision_priority2 > 0.0
+            && one_way_direction.collision_priority1 != one_way_direction.collision_priority2
+        {
+            let scale = collision_priority_recovery_scale(
+                body1,
+                body2,
+                one_way_direction.collision_priority1,
+                one_way_direction.collision_priority2,
+            );
+            for contact in context.solver_contacts.iter_mut() {
+                if contact.dist < 0.0 {
+                    contact.dist *= scale;
+                }
+            }
+        }
         // ghost collisions
         if body1.is_dynamic() && !body2.is_dynamic() {
             let normal = *context.normal;
@@ -203,3 +253,79 @@ impl<'a> PhysicsHooks for PhysicsHooksCollisionFilter<'a> {
         }
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rapier_wrapper::ANG_ZERO;
+    fn create_bodies(
+        physics_engine: &mut PhysicsEngine,
+        mass1: Real,
+        mass2: Real,
+    ) -> (RigidBodyHandle, RigidBodyHandle) {
+        let world_handle = 1;
+        physics_engine.world_create(
+            &WorldSettings {
+                particle_radius: 1.0,
+                smoothing_factor: 1.0,
+                counters_enabled: false,
+            },
+            world_handle,
+        );
+        let mut bodies = Vec::default();
+        for mass in [mass1, mass2] {
+            let body_handle = physics_engine.body_create(
+                world_handle,
+                Vector::zeros(),
+                Rotation::identity(),
+                BodyType::Dynamic,
+                0.0,
+                0.0,
+                0.0,
+                0,
+            );
+            physics_engine.body_set_mass_properties(
+                world_handle,
+                body_handle,
+                mass,
+                ANG_ZERO,
+                Vector::zeros(),
+                false,
+                true,
+            );
+            bodies.push(body_handle);
+        }
+        (bodies[0], bodies[1])
+    }
+    fn recovery_scale(mass1: Real, mass2: Real, priority1: Real, priority2: Real) -> Real {
+        let mut physics_engine = PhysicsEngine::default();
+        let (body1, body2) = create_bodies(&mut physics_engine, mass1, mass2);
+        let rigid_body_set = &physics_engine
+            .get_world(1)
+            .unwrap()
+            .physics_objects
+            .rigid_body_set;
+        collision_priority_recovery_scale(
+            &rigid_body_set[body1],
+            &rigid_body_set[body2],
+            priority1,
+            priority2,
+        )
+    }
+    #[test]
+    fn test_collision_priority_same_priority_keeps_recovery() {
+        assert_eq!(recovery_scale(1.0, 3.0, 2.0, 2.0), 1.0);
+    }
+    #[test]
+    fn test_collision_priority_limits_push_of_higher_priority_body() {
+        // Same mass, each body gets half of the recovery. The second body has the lower
+        // priority and should get three quarters of it, the first body only one quarter.
+        assert_eq!(recovery_scale(1.0, 1.0, 3.0, 1.0), 0.5);
+        assert_eq!(recovery_scale(1.0, 1.0, 1.0, 3.0), 0.5);
+        // The lighter first body gets three quarters of the recovery by mass, but only one quarter by priority.
+        let scale = recovery_scale(1.0, 3.0, 3.0, 1.0);
+        assert!((scale - 1.0 / 3.0).abs() < 1e-5);
+        // The heavier first body already gets no more than its share by priority.
+        let scale = recovery_scale(3.0, 1.0, 3.0, 1.0);
+        assert!((scale - 1.0).abs() < 1e-5);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "dim3")]
+use std::cell::RefCell;
 use std::num::NonZeroUsize;
 
 use hashbrown::HashMap;
@@ -38,6 +40,9 @@ pub struct ContactPointInfo {
     pub pixel_distance: Real,
     pub pixel_impulse: Real,
     pub pixel_tangent_impulse: TangentImpulse<Real>,
+    pub feature_id_1: u32,
+    pub feature_id_2: u32,
+    pub friction: Real,
 }
 #[cfg_attr(
     feature = "serde-serialize",
@@ -267,6 +272,12 @@ impl PhysicsWorld {
                     for manifold in &contact_pair.manifolds {
                         let manifold_normal = manifold.data.normal;
                         contact_info.normal = manifold_normal;
+                        // The friction is the same for every solver contact of the manifold.
+                        contact_info.friction = manifold
+                            .data
+                            .solver_contacts
+                            .first()
+                            .map_or(0.0, |solver_contact| solver_contact.friction);
                         // Read the geometric contacts.
                         for contact_point in &manifold.points {
                             if contact_point.dist
@@ -288,6 +299,8 @@ impl PhysicsWorld {
                             contact_info.pixel_distance = contact_point.dist;
                             contact_info.pixel_impulse = contact_point.data.impulse;
                             contact_info.pixel_tangent_impulse = contact_point.data.tangent_impulse;
+                            contact_info.feature_id_1 = contact_point.fid1.0;
+                            contact_info.feature_id_2 = contact_point.fid2.0;
                             space.contact_point_callback(
                                 &contact_info,
                                 &event_info,
@@ -472,8 +485,43 @@ impl PhysicsWorld {
         None
     }
 
-    // TODO multibody joints
-    /*
+    // A multibody link gets its degrees of freedom from the locked axes when it is inserted,
+    // so a multibody joint whose locked axes change is removed and inserted again instead of
+    // being changed in place. Returns the handle of the joint after the change.
+    pub fn modify_joint(
+        &mut self,
+        handle: JointHandle,
+        modify: impl FnOnce(&mut GenericJoint),
+    ) -> JointHandle {
+        if !handle.multibody {
+            if let Some(joint) = self.get_mut_joint(handle) {
+                modify(joint);
+            }
+            return handle;
+        }
+        let Some(previous_joint) = self.get_joint(handle).copied() else {
+            return handle;
+        };
+        let mut joint = previous_joint;
+        modify(&mut joint);
+        if joint.locked_axes == previous_joint.locked_axes {
+            if let Some(data) = self.get_mut_joint(handle) {
+                *data = joint;
+            }
+            return handle;
+        }
+        let Some((body_1, body_2)) = self.get_joint_bodies(handle) else {
+            return handle;
+        };
+        self.remove_joint(handle);
+        let new_handle = self.insert_joint(body_1, body_2, true, handle.kinematic, joint);
+        if new_handle != JointHandle::default() {
+            return new_handle;
+        }
+        // The new axes can't be simulated by the multibody, keep the previous joint.
+        self.insert_joint(body_1, body_2, true, handle.kinematic, previous_joint)
+    }
+
     pub fn get_joint(&self, handle: JointHandle) -> Option<&GenericJoint> {
         match handle.multibody {
             false => {
@@ -498,7 +546,33 @@ impl PhysicsWorld {
             }
         }
         None
-    } */
+    }
+
+    pub fn get_joint_bodies(
+        &self,
+        handle: JointHandle,
+    ) -> Option<(RigidBodyHandle, RigidBodyHandle)> {
+        match handle.multibody {
+            false => {
+                let joint = self
+                    .physics_objects
+                    .impulse_joint_set
+                    .get(ImpulseJointHandle(handle.index))?;
+                Some((joint.body1, joint.body2))
+            }
+            true => {
+                // The joint of a multibody link attaches it to its parent link.
+                let (multibody, link_id) = self
+                    .physics_objects
+                    .multibody_joint_set
+                    .get(MultibodyJointHandle(handle.index))?;
+                let link = multibody.link(link_id)?;
+                let parent = multibody.link(link.parent_id()?)?;
+                Some((parent.rigid_body_handle(), link.rigid_body_handle()))
+            }
+        }
+    }
+
     pub fn get_impulse_joint(&self, handle: JointHandle) -> Option<&ImpulseJoint> {
         match handle.multibody {
             false => {
@@ -533,6 +607,10 @@ impl PhysicsWorld {
 pub struct PhysicsEngine {
     pub physics_worlds: HashMap<RapierId, PhysicsWorld>,
     pub shapes: HashMap<RapierId, SharedShape>,
+    // Last skewed versions of each shape, most recent first, converting a shape to a hull or a trimesh is too slow
+    // to redo on every query. A shape can be used with different skews at the same time, see MAX_SKEWED_SHAPES.
+    #[cfg(feature = "dim3")]
+    pub(crate) skewed_shapes: RefCell<HashMap<RapierId, Vec<(Matrix<Real>, SharedShape)>>>,
 }
 impl PhysicsEngine {
     pub fn get_mut_world(&mut self, world_handle: WorldHandle) -> Option<&mut PhysicsWorld> {
@@ -545,10 +623,14 @@ impl PhysicsEngine {
 
     pub fn insert_shape(&mut self, shape: SharedShape, handle: ShapeHandle) {
         self.shapes.insert(handle, shape);
+        #[cfg(feature = "dim3")]
+        self.skewed_shapes.get_mut().remove(&handle);
     }
 
     pub fn remove_shape(&mut self, shape_handle: ShapeHandle) {
         self.shapes.remove_entry(&shape_handle);
+        #[cfg(feature = "dim3")]
+        self.skewed_shapes.get_mut().remove(&shape_handle);
     }
 
     pub fn get_shape(&self, shape_handle: ShapeHandle) -> Option<&SharedShape> {
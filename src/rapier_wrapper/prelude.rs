@@ -1,4 +1,5 @@
 pub use super::body::*;
+pub use super::character_controller::*;
 pub use super::collider::*;
 pub use super::convert::*;
 pub use super::event_handler::*;
@@ -9,3 +10,5 @@ pub use super::query::*;
 pub use super::settings::*;
 pub use super::shape::*;
 pub use super::user_data::*;
+#[cfg(feature = "dim3")]
+pub use super::vehicle::*;
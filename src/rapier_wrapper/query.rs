@@ -234,10 +234,8 @@ impl PhysicsEngine {
         shape_info2: ShapeInfo,
     ) -> ShapeCastResult {
         let mut result = ShapeCastResult::new();
-        if let Some(raw_shared_shape1) = self.get_shape(shape_info1.handle) {
-            let shared_shape1 = scale_shape(raw_shared_shape1, shape_info1);
-            if let Some(raw_shared_shape2) = self.get_shape(shape_info2.handle) {
-                let shared_shape2 = scale_shape(raw_shared_shape2, shape_info2);
+        if let Some(shared_shape1) = self.get_scaled_shape(shape_info1) {
+            if let Some(shared_shape2) = self.get_scaled_shape(shape_info2) {
                 let shape_transform1 = shape_info1.transform;
                 let shape_transform2 = shape_info2.transform;
                 let shape_cast_options = ShapeCastOptions {
@@ -329,8 +327,8 @@ impl PhysicsEngine {
         needs_exact: bool,
     ) -> ShapeCastResult {
         let mut result = ShapeCastResult::new();
-        if let Some(raw_shared_shape) = self.get_shape(shape_info.handle) {
-            let shared_shape = scale_shape(raw_shared_shape, shape_info);
+        let margin = margin + shape_info.pixel_margin;
+        if let Some(shared_shape) = self.get_scaled_shape(shape_info) {
             if let Some(physics_world) = self.get_world(world_handle) {
                 let shape_transform = shape_info.transform;
                 let mut filter = QueryFilter::new();
@@ -528,11 +526,12 @@ impl PhysicsEngine {
         margin: Real,
     ) -> ContactResult {
         let mut result = ContactResult::default();
-        let prediction = Real::max(0.002, margin);
-        if let Some(raw_shared_shape1) = self.get_shape(shape_info1.handle) {
-            let shared_shape1 = scale_shape(raw_shared_shape1, shape_info1);
-            if let Some(raw_shared_shape2) = self.get_shape(shape_info2.handle) {
-                let shared_shape2 = scale_shape(raw_shared_shape2, shape_info2);
+        let prediction = Real::max(
+            0.002,
+            margin + shape_info1.pixel_margin + shape_info2.pixel_margin,
+        );
+        if let Some(shared_shape1) = self.get_scaled_shape(shape_info1) {
+            if let Some(shared_shape2) = self.get_scaled_shape(shape_info2) {
                 let shape_transform1 = shape_info1.transform;
                 let shape_transform2 = shape_info2.transform;
                 match parry::query::contact(
@@ -564,4 +563,51 @@ impl PhysicsEngine {
         }
         result
     }
+
+    // Contact of a separation ray going from `from` to `to` against a shape, with the same convention as shapes_contact.
+    // The ray only separates along its direction, or along the hit normal when sliding on slopes.
+    pub fn separation_ray_contact(
+        &self,
+        pixel_from: Vector<Real>,
+        pixel_to: Vector<Real>,
+        shape_info: ShapeInfo,
+        slide_on_slope: bool,
+    ) -> ContactResult {
+        let mut result = ContactResult::default();
+        let pixel_ray = pixel_to - pixel_from;
+        let pixel_length = pixel_ray.norm();
+        if pixel_length <= DEFAULT_EPSILON {
+            return result;
+        }
+        let Some(shared_shape) = self.get_scaled_shape(shape_info) else {
+            return result;
+        };
+        let ray = Ray::new(Point { coords: pixel_from }, pixel_ray / pixel_length);
+        let Some(intersection) =
+            shared_shape.cast_ray_and_get_normal(&shape_info.transform, &ray, pixel_length, true)
+        else {
+            return result;
+        };
+        let support_a = pixel_to;
+        let mut support_b = ray.point_at(intersection.time_of_impact).coords;
+        if slide_on_slope {
+            let normal = intersection.normal;
+            support_b = support_a + normal * (support_b - support_a).dot(&normal);
+        }
+        let separation = support_a - support_b;
+        let depth = separation.norm();
+        let normal = if depth > DEFAULT_EPSILON {
+            separation / depth
+        } else {
+            ray.dir
+        };
+        result.collided = true;
+        result.within_margin = false;
+        result.pixel_distance = -depth;
+        result.pixel_point1 = support_a;
+        result.pixel_point2 = support_b;
+        result.normal1 = normal;
+        result.normal2 = -normal;
+        result
+    }
 }
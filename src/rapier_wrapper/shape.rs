@@ -1,3 +1,5 @@
+use rapier::parry::transformation::vhacd::VHACDParameters;
+use rapier::parry::transformation::vhacd::VHACD;
 use rapier::prelude::*;
 use types::Transform;
 
@@ -24,7 +26,12 @@ pub struct ShapeInfo {
     pub transform: Isometry<Real>,
     #[cfg(feature = "dim2")]
     pub skew: Real,
+    // What is left of the basis once the rotation and the scale are removed, applied before the scale.
+    #[cfg(feature = "dim3")]
+    pub skew: Matrix<Real>,
     pub scale: Vector<Real>,
+    // Margin of the shape, added to the prediction distance of queries.
+    pub pixel_margin: Real,
 }
 #[cfg(feature = "dim2")]
 pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transform) -> ShapeInfo {
@@ -34,10 +41,12 @@ pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transfor
         transform: Isometry2::new(vector_to_rapier(transform.origin), transform.rotation()),
         skew: transform.skew(),
         scale: vector_to_rapier(transform.scale()),
+        pixel_margin: 0.0,
     }
 }
 #[cfg(feature = "dim3")]
 pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transform) -> ShapeInfo {
+    use godot::builtin::math::FloatExt;
     use nalgebra::Isometry3;
     use nalgebra::Quaternion;
     use nalgebra::Translation3;
@@ -50,10 +59,24 @@ pub fn shape_info_from_body_shape(shape_handle: ShapeHandle, transform: Transfor
     ));
     let translation = Translation3::from(vector_to_rapier(transform.origin));
     let isometry = Isometry3::from_parts(translation, rotation);
+    let scale = vector_to_rapier(transform.basis.scale());
+    let mut skew = Matrix::identity();
+    if !scale.iter().any(|s| s.is_zero_approx()) {
+        let basis = Matrix::from_columns(&[
+            vector_to_rapier(transform.basis.col_a()),
+            vector_to_rapier(transform.basis.col_b()),
+            vector_to_rapier(transform.basis.col_c()),
+        ]);
+        skew = Matrix::from_diagonal(&scale.map(|s| 1.0 / s))
+            * rotation.to_rotation_matrix().matrix().transpose()
+            * basis;
+    }
     ShapeInfo {
         handle: shape_handle,
         transform: isometry,
-        scale: vector_to_rapier(transform.basis.scale()),
+        skew,
+        scale,
+        pixel_margin: 0.0,
     }
 }
 impl PhysicsEngine {
@@ -196,43 +219,74 @@ impl PhysicsEngine {
     }
 
     #[cfg(feature = "dim3")]
+    #[allow(clippy::too_many_arguments)]
     pub fn shape_create_heightmap(
         &mut self,
         heights: &[Real],
         width: i32,
         depth: i32,
+        cell_scale: Vector<Real>,
+        fix_internal_edges: bool,
+        cell_holes: &[bool],
         handle: ShapeHandle,
     ) {
-        use nalgebra::Vector3;
+        use rapier::parry::shape::HeightFieldCellStatus;
         let width = width as usize;
         let depth = depth as usize;
-        // Create the height matrix from the input heights
-        let original_heights = DMatrix::from_fn(width, depth, |i, j| heights[j * width + i]);
-        // Transpose the matrix to swap width and depth
-        let rotated_heights = original_heights.transpose();
-        // The new dimensions after rotating the heightmap
-        let new_width = depth;
-        let new_depth = width;
-        // Create the shape with the rotated dimensions
-        let shape = SharedShape::heightfield_with_flags(
+        // NaN heights are holes, replace them with the lowest valid height so the aabb stays finite
+        let fill_height = heights
+            .iter()
+            .filter(|h| !h.is_nan())
+            .fold(None, |min: Option<Real>, h| {
+                Some(min.map_or(*h, |min| min.min(*h)))
+            })
+            .unwrap_or(0.0);
+        // Rows of the heightfield go along z (depth), columns along x (width)
+        let rotated_heights = DMatrix::from_fn(depth, width, |i, j| {
+            let h = heights[i * width + j];
+            if h.is_nan() {
+                fill_height
+            } else {
+                h
+            }
+        });
+        let mut flags = HeightFieldFlags::empty();
+        if fix_internal_edges {
+            flags |= HeightFieldFlags::FIX_INTERNAL_EDGES;
+        }
+        let mut heightfield = HeightField::with_flags(
             rotated_heights,
-            Vector3::new(new_depth as Real, 1.0, new_width as Real),
-            HeightFieldFlags::FIX_INTERNAL_EDGES,
+            Vector::new(
+                (width - 1) as Real * cell_scale.x,
+                cell_scale.y,
+                (depth - 1) as Real * cell_scale.z,
+            ),
+            flags,
         );
-        self.insert_shape(shape, handle)
-    }
-
-    #[cfg(feature = "dim3")]
-    pub fn shape_get_heightmap(&self, shape_handle: ShapeHandle) -> (DMatrix<Real>, i32, i32) {
-        if let Some(shape) = self.get_shape(shape_handle) {
-            if let Some(shape) = shape.as_heightfield() {
-                let scale = shape.scale();
-                let depth = scale.x as i32;
-                let width = scale.z as i32;
-                return (shape.heights().clone(), depth, width);
+        let (cell_rows, cell_cols) = heightfield.num_cells_ij();
+        for i in 0..cell_rows {
+            for j in 0..cell_cols {
+                let masked = cell_holes.get(i * cell_cols + j).copied().unwrap_or(false);
+                let has_nan = heights[i * width + j].is_nan()
+                    || heights[i * width + j + 1].is_nan()
+                    || heights[(i + 1) * width + j].is_nan()
+                    || heights[(i + 1) * width + j + 1].is_nan();
+                if masked || has_nan {
+                    heightfield.set_cell_status(i, j, HeightFieldCellStatus::CELL_REMOVED);
+                }
             }
         }
-        (DMatrix::default(), 0, 0)
+        self.insert_shape(SharedShape::new(heightfield), handle)
+    }
+
+    pub fn shape_create_segment(
+        &mut self,
+        pixel_a: Vector<Real>,
+        pixel_b: Vector<Real>,
+        handle: ShapeHandle,
+    ) {
+        let shape = SharedShape::segment(Point { coords: pixel_a }, Point { coords: pixel_b });
+        self.insert_shape(shape, handle);
     }
 
     #[cfg(feature = "dim2")]
@@ -263,6 +317,41 @@ impl PhysicsEngine {
         self.insert_shape(shape.unwrap(), handle)
     }
 
+    // Same as SharedShape::convex_decomposition_with_params, but doesn't panic when no convex part could be built.
+    pub fn shape_create_convex_decomposition(
+        &mut self,
+        points: &Vec<Vector<Real>>,
+        indices: &[[u32; DIM]],
+        params: &VHACDParameters,
+        handle: ShapeHandle,
+    ) -> bool {
+        let points_vec = point_array_to_vec(points);
+        // VHACD voxelizes the points aabb and panics if it is flat along an axis.
+        let aabb = Aabb::from_points(&points_vec);
+        if aabb.extents().min() <= DEFAULT_EPSILON {
+            return false;
+        }
+        let decomposition = VHACD::decompose(params, &points_vec, indices, true);
+        let mut parts = Vec::new();
+        #[cfg(feature = "dim2")]
+        for vertices in decomposition.compute_exact_convex_hulls(&points_vec, indices) {
+            if let Some(convex) = SharedShape::convex_polyline(vertices) {
+                parts.push((Isometry::identity(), convex));
+            }
+        }
+        #[cfg(feature = "dim3")]
+        for (vertices, indices) in decomposition.compute_exact_convex_hulls(&points_vec, indices) {
+            if let Some(convex) = SharedShape::convex_mesh(vertices, &indices) {
+                parts.push((Isometry::identity(), convex));
+            }
+        }
+        if parts.is_empty() {
+            return false;
+        }
+        self.insert_shape(SharedShape::compound(parts), handle);
+        true
+    }
+
     #[cfg(feature = "dim2")]
     pub fn shape_get_concave_polyline(
         &self,
@@ -0,0 +1,90 @@
+use rapier::control::DynamicRayCastVehicleController;
+use rapier::prelude::*;
+
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsCollisionObjects;
+use crate::servers::rapier_physics_singleton::PhysicsIds;
+use crate::spaces::rapier_space::RapierSpace;
+pub struct VehicleWheelState {
+    pub in_contact: bool,
+    pub pixel_contact_point: Vector<Real>,
+    pub contact_normal: Vector<Real>,
+    pub pixel_hard_point: Vector<Real>,
+    pub pixel_suspension_length: Real,
+    pub suspension_force: Real,
+    pub forward_impulse: Real,
+    pub side_impulse: Real,
+    pub rotation: Real,
+    pub ground_collider: Option<ColliderHandle>,
+    pub ground_user_data: UserData,
+}
+impl PhysicsEngine {
+    #[allow(clippy::too_many_arguments)]
+    pub fn vehicle_update(
+        &mut self,
+        world_handle: WorldHandle,
+        vehicle: &mut DynamicRayCastVehicleController,
+        dt: Real,
+        handle_excluded_info: &QueryExcludedInfo,
+        physics_collision_objects: &PhysicsCollisionObjects,
+        physics_ids: &PhysicsIds,
+        space: &RapierSpace,
+    ) {
+        let Some(physics_world) = self.get_mut_world(world_handle) else {
+            return;
+        };
+        let physics_objects = &mut physics_world.physics_objects;
+        if !physics_objects.rigid_body_set.contains(vehicle.chassis) {
+            return;
+        }
+        let predicate = |handle: ColliderHandle, collider: &Collider| -> bool {
+            !space.is_handle_excluded_callback(
+                handle,
+                &UserData::new(collider.user_data),
+                handle_excluded_info,
+                physics_collision_objects,
+                physics_ids,
+            )
+        };
+        let mut filter = QueryFilter::new()
+            .exclude_sensors()
+            .exclude_rigid_body(vehicle.chassis);
+        filter.predicate = Some(&predicate);
+        vehicle.update_vehicle(
+            dt,
+            &mut physics_objects.rigid_body_set,
+            &physics_objects.collider_set,
+            &physics_objects.query_pipeline,
+            filter,
+        );
+    }
+
+    pub fn vehicle_get_wheel_state(
+        &self,
+        world_handle: WorldHandle,
+        vehicle: &DynamicRayCastVehicleController,
+        wheel_index: usize,
+    ) -> Option<VehicleWheelState> {
+        let wheel = vehicle.wheels().get(wheel_index)?;
+        let raycast_info = wheel.raycast_info();
+        let mut state = VehicleWheelState {
+            in_contact: raycast_info.is_in_contact,
+            pixel_contact_point: raycast_info.contact_point_ws.coords,
+            contact_normal: raycast_info.contact_normal_ws,
+            pixel_hard_point: raycast_info.hard_point_ws.coords,
+            pixel_suspension_length: raycast_info.suspension_length,
+            suspension_force: wheel.wheel_suspension_force,
+            forward_impulse: wheel.forward_impulse,
+            side_impulse: wheel.side_impulse,
+            rotation: wheel.rotation,
+            ground_collider: raycast_info.ground_object,
+            ground_user_data: UserData::invalid_user_data(),
+        };
+        if let Some(ground_collider) = raycast_info.ground_object
+            && let Some(physics_world) = self.get_world(world_handle)
+        {
+            state.ground_user_data = physics_world.get_collider_user_data(ground_collider);
+        }
+        Some(state)
+    }
+}
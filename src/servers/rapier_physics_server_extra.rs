@@ -4,20 +4,60 @@ use godot::prelude::*;
 
 use super::rapier_physics_singleton::physics_data;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
+use crate::bodies::rapier_collision_object_base::CollisionObjectShapeMaterial;
 use crate::fluids::rapier_fluid::RapierFluid;
+use crate::joints::rapier_joint::RapierJoint;
+use crate::rapier_wrapper::prelude::*;
 use crate::servers::RapierPhysicsServer;
 use crate::types::*;
 pub enum RapierBodyParam {
     ContactSkin,
+    FrictionCombineRule,
+    RestitutionCombineRule,
+    SleepLinearThreshold,
+    SleepAngularThreshold,
+    TimeBeforeSleep,
+    CcdMode,
+    SoftCcdPrediction,
+    DominanceGroup,
+    MassFromDensity,
+    AdditionalMass,
 }
 impl RapierBodyParam {
     fn from_i32(value: i32) -> RapierBodyParam {
         match value {
             0 => RapierBodyParam::ContactSkin,
+            1 => RapierBodyParam::FrictionCombineRule,
+            2 => RapierBodyParam::RestitutionCombineRule,
+            3 => RapierBodyParam::SleepLinearThreshold,
+            4 => RapierBodyParam::SleepAngularThreshold,
+            5 => RapierBodyParam::TimeBeforeSleep,
+            6 => RapierBodyParam::CcdMode,
+            7 => RapierBodyParam::SoftCcdPrediction,
+            8 => RapierBodyParam::DominanceGroup,
+            9 => RapierBodyParam::MassFromDensity,
+            10 => RapierBodyParam::AdditionalMass,
             _ => RapierBodyParam::ContactSkin,
         }
     }
 }
+pub enum RapierJointParam {
+    Multibody,
+    SolverPriority,
+    NaturalFrequency,
+    DampingRatio,
+}
+impl RapierJointParam {
+    fn from_i32(value: i32) -> RapierJointParam {
+        match value {
+            0 => RapierJointParam::Multibody,
+            1 => RapierJointParam::SolverPriority,
+            2 => RapierJointParam::NaturalFrequency,
+            3 => RapierJointParam::DampingRatio,
+            _ => RapierJointParam::Multibody,
+        }
+    }
+}
 #[godot_api]
 impl RapierPhysicsServer {
     #[func]
@@ -30,6 +70,8 @@ impl RapierPhysicsServer {
                     RapierBodyParam::from_i32(param),
                     value,
                     &mut physics_data.physics_engine,
+                    &mut physics_data.spaces,
+                    &physics_data.ids,
                 );
             }
         }
@@ -47,6 +89,534 @@ impl RapierPhysicsServer {
         0.0.to_variant()
     }
 
+    #[func]
+    /// Override the material of a single shape of a body. Supported keys are `friction`, `bounce` and `contact_skin`, missing or null keys use the body value.
+    fn body_set_shape_material(body: Rid, shape_idx: i32, material: Dictionary) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+        {
+            let material = CollisionObjectShapeMaterial {
+                friction: material
+                    .get("friction")
+                    .and_then(|value| variant_to_optional_float(&value)),
+                bounce: material
+                    .get("bounce")
+                    .and_then(|value| variant_to_optional_float(&value)),
+                contact_skin: material
+                    .get("contact_skin")
+                    .and_then(|value| variant_to_optional_float(&value)),
+            };
+            body.set_shape_material(
+                shape_idx as usize,
+                material,
+                &mut physics_data.physics_engine,
+            );
+        }
+    }
+
+    #[func]
+    /// Get the material overrides of a single shape of a body.
+    fn body_get_shape_material(body: Rid, shape_idx: i32) -> Dictionary {
+        let mut dictionary = Dictionary::new();
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body) {
+            let material = body.get_base().get_shape_material(shape_idx as usize);
+            if let Some(friction) = material.friction {
+                dictionary.set("friction", friction);
+            }
+            if let Some(bounce) = material.bounce {
+                dictionary.set("bounce", bounce);
+            }
+            if let Some(contact_skin) = material.contact_skin {
+                dictionary.set("contact_skin", contact_skin);
+            }
+        }
+        dictionary
+    }
+
+    #[func]
+    /// Set the density of a single shape of a body, used when the mass is computed from the shape densities.
+    fn body_set_shape_density(body: Rid, shape_idx: i32, density: real) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+        {
+            body.set_shape_density(
+                shape_idx as usize,
+                density,
+                &mut physics_data.physics_engine,
+                &mut physics_data.spaces,
+                &physics_data.ids,
+            );
+        }
+    }
+
+    #[func]
+    /// Get the density of a single shape of a body.
+    fn body_get_shape_density(body: Rid, shape_idx: i32) -> real {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body) {
+            return body.get_base().get_shape_density(shape_idx as usize);
+        }
+        1.0
+    }
+
+    #[func]
+    /// Move a body with the kinematic character controller. Supported option keys are `up`, `offset`, `slide`, `autostep_max_height`, `autostep_min_width`, `autostep_include_dynamic_bodies`, `max_slope_climb_angle`, `min_slope_slide_angle`, `snap_to_ground`, `normal_nudge_factor`, `apply_impulses`, `mass` and `delta`, which defaults to the last step of the space. All the enabled shapes of the body are moved together.
+    /// Returns a dictionary with `translation`, `grounded`, `is_sliding_down_slope` and `collisions`. The body is not moved, the returned translation has to be applied by the caller.
+    fn character_move(body: Rid, desired_translation: Vector, options: Dictionary) -> Dictionary {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body)
+            && let Some(body) = body.get_body()
+            && let Some(space) = physics_data
+                .spaces
+                .get(&body.get_base().get_space(&physics_data.ids))
+        {
+            return space.character_move(
+                body,
+                desired_translation,
+                &options,
+                &mut physics_data.physics_engine,
+                &physics_data.shapes,
+                &physics_data.ids,
+                &physics_data.collision_objects,
+            );
+        }
+        Dictionary::new()
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Attach a raycast vehicle controller to a body. The controller is stepped with the space, the vehicle drives along the local Z axis of the body.
+    fn vehicle_create(body: Rid) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+        {
+            body.set_vehicle_enabled(true, &mut physics_data.spaces, &physics_data.ids);
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Remove the vehicle controller of a body, with all its wheels.
+    fn vehicle_destroy(body: Rid) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+        {
+            body.set_vehicle_enabled(false, &mut physics_data.spaces, &physics_data.ids);
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Add a wheel to the vehicle of a body and return its index, or -1 if the body has no vehicle. Supported keys are `chassis_connection_point`, `direction`, `axle`, `suspension_rest_length`, `radius`, `suspension_stiffness`, `suspension_compression`, `suspension_damping`, `max_suspension_travel`, `max_suspension_force`, `friction_slip` and `side_friction_stiffness`, all in body local space.
+    fn vehicle_add_wheel(body: Rid, wheel: Dictionary) -> i32 {
+        use rapier::control::WheelTuning;
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+            && let Some(vehicle) = body.get_mut_vehicle()
+        {
+            let get_float = |key: &str, default: real| {
+                wheel
+                    .get(key)
+                    .map_or(default, |value| variant_to_float(&value))
+            };
+            let get_vector = |key: &str, default: Vector| {
+                wheel
+                    .get(key)
+                    .and_then(|value| value.try_to::<Vector>().ok())
+                    .unwrap_or(default)
+            };
+            let default_tuning = WheelTuning::default();
+            let tuning = WheelTuning {
+                suspension_stiffness: get_float(
+                    "suspension_stiffness",
+                    default_tuning.suspension_stiffness,
+                ),
+                suspension_compression: get_float(
+                    "suspension_compression",
+                    default_tuning.suspension_compression,
+                ),
+                suspension_damping: get_float(
+                    "suspension_damping",
+                    default_tuning.suspension_damping,
+                ),
+                max_suspension_travel: get_float(
+                    "max_suspension_travel",
+                    default_tuning.max_suspension_travel,
+                ),
+                side_friction_stiffness: get_float(
+                    "side_friction_stiffness",
+                    default_tuning.side_friction_stiffness,
+                ),
+                friction_slip: get_float("friction_slip", default_tuning.friction_slip),
+                max_suspension_force: get_float(
+                    "max_suspension_force",
+                    default_tuning.max_suspension_force,
+                ),
+            };
+            vehicle.add_wheel(
+                vector_to_rapier(get_vector("chassis_connection_point", Vector::ZERO)).into(),
+                vector_to_rapier(get_vector("direction", Vector::DOWN).normalized()),
+                vector_to_rapier(get_vector("axle", Vector::LEFT).normalized()),
+                get_float("suspension_rest_length", 0.15),
+                get_float("radius", 0.5),
+                &tuning,
+            );
+            return vehicle.wheels().len() as i32 - 1;
+        }
+        -1
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Get the number of wheels of the vehicle of a body.
+    fn vehicle_get_wheel_count(body: Rid) -> i32 {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body)
+            && let Some(body) = body.get_body()
+            && let Some(vehicle) = body.get_vehicle()
+        {
+            return vehicle.wheels().len() as i32;
+        }
+        0
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Set the engine force applied by a wheel of a vehicle.
+    fn vehicle_set_wheel_engine_force(body: Rid, wheel: i32, engine_force: real) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+            && let Some(vehicle) = body.get_mut_vehicle()
+            && let Some(wheel) = vehicle.wheels_mut().get_mut(wheel as usize)
+        {
+            wheel.engine_force = engine_force;
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Set the brake force applied by a wheel of a vehicle.
+    fn vehicle_set_wheel_brake(body: Rid, wheel: i32, brake: real) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+            && let Some(vehicle) = body.get_mut_vehicle()
+            && let Some(wheel) = vehicle.wheels_mut().get_mut(wheel as usize)
+        {
+            wheel.brake = brake;
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Set the steering angle of a wheel of a vehicle, in radians.
+    fn vehicle_set_wheel_steering(body: Rid, wheel: i32, steering: real) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+            && let Some(vehicle) = body.get_mut_vehicle()
+            && let Some(wheel) = vehicle.wheels_mut().get_mut(wheel as usize)
+        {
+            wheel.steering = steering;
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Get the forward speed of the vehicle of a body, negative when moving backwards.
+    fn vehicle_get_current_speed(body: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body)
+            && let Some(body) = body.get_body()
+            && let Some(vehicle) = body.get_vehicle()
+        {
+            return vehicle.current_vehicle_speed;
+        }
+        0.0
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Get the state of a wheel of a vehicle, updated at the last step. Contains `in_contact`, `contact_point`, `contact_normal`, `hard_point`, `suspension_length`, `suspension_force`, `forward_impulse`, `side_impulse`, `rotation`, `engine_force`, `brake`, `steering`, `collider` and `collider_shape`.
+    fn vehicle_get_wheel_state(body: Rid, wheel: i32) -> Dictionary {
+        use crate::bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+        let mut dictionary = Dictionary::new();
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body)
+            && let Some(body) = body.get_body()
+            && let Some(vehicle) = body.get_vehicle()
+            && let Some(wheel_state) = physics_data.physics_engine.vehicle_get_wheel_state(
+                body.get_base().get_space_id(),
+                vehicle,
+                wheel as usize,
+            )
+        {
+            let wheel = &vehicle.wheels()[wheel as usize];
+            dictionary.set("in_contact", wheel_state.in_contact);
+            dictionary.set(
+                "contact_point",
+                vector_to_godot(wheel_state.pixel_contact_point),
+            );
+            dictionary.set(
+                "contact_normal",
+                vector_to_godot(wheel_state.contact_normal),
+            );
+            dictionary.set("hard_point", vector_to_godot(wheel_state.pixel_hard_point));
+            dictionary.set("suspension_length", wheel_state.pixel_suspension_length);
+            dictionary.set("suspension_force", wheel_state.suspension_force);
+            dictionary.set("forward_impulse", wheel_state.forward_impulse);
+            dictionary.set("side_impulse", wheel_state.side_impulse);
+            dictionary.set("rotation", wheel_state.rotation);
+            dictionary.set("engine_force", wheel.engine_force);
+            dictionary.set("brake", wheel.brake);
+            dictionary.set("steering", wheel.steering);
+            let mut collider = Rid::Invalid;
+            let mut collider_shape = -1;
+            if wheel_state.ground_collider.is_some() {
+                let (rid, shape_index) = RapierCollisionObjectBase::get_collider_user_data(
+                    &wheel_state.ground_user_data,
+                    &physics_data.ids,
+                );
+                collider = rid;
+                collider_shape = shape_index as i32;
+            }
+            dictionary.set("collider", collider);
+            dictionary.set("collider_shape", collider_shape);
+        }
+        dictionary
+    }
+
+    #[func]
+    /// Create a shape that decomposes a concave mesh in convex hulls, so it can be used on dynamic bodies. Its data is a dictionary with the same points as the concave polygon shape, under `faces` in 3D or `segments` in 2D, and the optional `resolution`, `concavity` and `max_convex_hulls` decomposition parameters.
+    fn convex_decomposition_shape_create() -> Rid {
+        let Ok(mut physics_singleton) =
+            PhysicsServer::singleton().try_cast::<RapierPhysicsServer>()
+        else {
+            return Rid::Invalid;
+        };
+        physics_singleton
+            .bind_mut()
+            .implementation
+            .convex_decomposition_shape_create()
+    }
+
+    #[func]
+    /// Set an extra parameter for a joint.
+    fn joint_set_extra_param(joint: Rid, param: i32, value: Variant) {
+        use crate::joints::rapier_joint::IRapierJoint;
+        let physics_data = physics_data();
+        if let Some(joint) = physics_data.joints.get_mut(&joint) {
+            let param = RapierJointParam::from_i32(param);
+            if matches!(param, RapierJointParam::SolverPriority)
+                && let Some(space) = physics_data
+                    .spaces
+                    .get_mut(&joint.get_base().get_space(&physics_data.ids))
+            {
+                space.mark_joints_order_dirty();
+            }
+            joint
+                .get_mut_base()
+                .set_extra_param(param, value, &mut physics_data.physics_engine);
+        }
+    }
+
+    #[func]
+    /// Get an extra parameter for a joint.
+    fn joint_get_extra_param(joint: Rid, param: i32) -> Variant {
+        use crate::joints::rapier_joint::IRapierJoint;
+        let physics_data = physics_data();
+        if let Some(joint) = physics_data.joints.get(&joint) {
+            return joint
+                .get_base()
+                .get_extra_param(RapierJointParam::from_i32(param));
+        }
+        0.0.to_variant()
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    #[allow(clippy::too_many_arguments)]
+    /// Set the motor driving a slider joint along its axis. With a stiffness of 0 the motor only drives the velocity, with the damping as factor.
+    fn slider_joint_set_linear_motor(
+        joint: Rid,
+        enabled: bool,
+        target_velocity: f32,
+        target_position: f32,
+        stiffness: f32,
+        damping: f32,
+        max_force: f32,
+    ) {
+        use crate::joints::rapier_slider_joint_3d::RapierSliderJointMotor;
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierSliderJoint3D(joint)) = physics_data.joints.get_mut(&joint) {
+            joint.set_linear_motor(
+                RapierSliderJointMotor {
+                    enabled,
+                    target_velocity,
+                    target_position,
+                    stiffness,
+                    damping,
+                    max_force,
+                },
+                &mut physics_data.physics_engine,
+            );
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    #[allow(clippy::too_many_arguments)]
+    /// Set the motor rotating a slider joint around its axis. The slider needs an angular limit range to rotate.
+    fn slider_joint_set_angular_motor(
+        joint: Rid,
+        enabled: bool,
+        target_velocity: f32,
+        target_position: f32,
+        stiffness: f32,
+        damping: f32,
+        max_force: f32,
+    ) {
+        use crate::joints::rapier_slider_joint_3d::RapierSliderJointMotor;
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierSliderJoint3D(joint)) = physics_data.joints.get_mut(&joint) {
+            joint.set_angular_motor(
+                RapierSliderJointMotor {
+                    enabled,
+                    target_velocity,
+                    target_position,
+                    stiffness,
+                    damping,
+                    max_force,
+                },
+                &mut physics_data.physics_engine,
+            );
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    #[func]
+    /// Get the motor of a slider joint, along its axis or around it.
+    fn slider_joint_get_motor(joint: Rid, angular: bool) -> Dictionary {
+        let mut dictionary = Dictionary::new();
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierSliderJoint3D(joint)) = physics_data.joints.get(&joint) {
+            let motor = if angular {
+                joint.get_angular_motor()
+            } else {
+                joint.get_linear_motor()
+            };
+            dictionary.set("enabled", motor.enabled);
+            dictionary.set("target_velocity", motor.target_velocity);
+            dictionary.set("target_position", motor.target_position);
+            dictionary.set("stiffness", motor.stiffness);
+            dictionary.set("damping", motor.damping);
+            dictionary.set("max_force", motor.max_force);
+        }
+        dictionary
+    }
+
+    #[func]
+    /// Create a fixed joint welding two bodies together. The frames are in the local space of each body.
+    fn joint_make_fixed(body_a: Rid, frame_a: Transform, body_b: Rid, frame_b: Transform) -> Rid {
+        let Ok(mut physics_singleton) =
+            PhysicsServer::singleton().try_cast::<RapierPhysicsServer>()
+        else {
+            return Rid::Invalid;
+        };
+        let mut physics_singleton = physics_singleton.bind_mut();
+        let rid = physics_singleton.implementation.joint_create();
+        physics_singleton
+            .implementation
+            .joint_make_fixed(rid, body_a, frame_a, body_b, frame_b);
+        rid
+    }
+
+    #[func]
+    /// Create a rope joint between two bodies, keeping the anchors at most max_length apart. The anchors are in the local space of each body.
+    fn joint_make_rope(
+        body_a: Rid,
+        anchor_a: Vector,
+        body_b: Rid,
+        anchor_b: Vector,
+        max_length: real,
+    ) -> Rid {
+        let Ok(mut physics_singleton) =
+            PhysicsServer::singleton().try_cast::<RapierPhysicsServer>()
+        else {
+            return Rid::Invalid;
+        };
+        let mut physics_singleton = physics_singleton.bind_mut();
+        let rid = physics_singleton.implementation.joint_create();
+        physics_singleton
+            .implementation
+            .joint_make_rope(rid, body_a, anchor_a, body_b, anchor_b, max_length);
+        rid
+    }
+
+    #[func]
+    /// Set the min length of a rope joint.
+    fn rope_joint_set_min_length(joint: Rid, min_length: real) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get_mut(&joint) {
+            joint.set_min_length(min_length, &mut physics_data.physics_engine);
+        }
+    }
+
+    #[func]
+    /// Get the min length of a rope joint.
+    fn rope_joint_get_min_length(joint: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get(&joint) {
+            return joint.get_min_length();
+        }
+        0.0
+    }
+
+    #[func]
+    /// Set the max length of a rope joint.
+    fn rope_joint_set_max_length(joint: Rid, max_length: real) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get_mut(&joint) {
+            joint.set_max_length(max_length, &mut physics_data.physics_engine);
+        }
+    }
+
+    #[func]
+    /// Get the max length of a rope joint.
+    fn rope_joint_get_max_length(joint: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get(&joint) {
+            return joint.get_max_length();
+        }
+        0.0
+    }
+
+    #[func]
+    /// Set the compliance of a rope joint, the inverse of its stiffness when stretched past the max length. 0 makes the rope inextensible.
+    fn rope_joint_set_compliance(joint: Rid, compliance: real) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get_mut(&joint) {
+            joint.set_compliance(compliance, &mut physics_data.physics_engine);
+        }
+    }
+
+    #[func]
+    /// Get the compliance of a rope joint.
+    fn rope_joint_get_compliance(joint: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierRopeJoint(joint)) = physics_data.joints.get(&joint) {
+            return joint.get_compliance();
+        }
+        0.0
+    }
+
     #[cfg(feature = "serde-serialize")]
     #[func]
     /// Exports the physics object to a JSON string. This is slower than the binary export.
@@ -132,8 +702,15 @@ impl RapierPhysicsServer {
                 joint.get_base().get_rid(),
                 &mut physics_data.ids,
             );
+            if let Some(space) = physics_data
+                .spaces
+                .get_mut(&joint.get_base().get_space(&physics_data.ids))
+            {
+                space.mark_joints_order_dirty();
+            }
         } else if let Some(space) = physics_data.spaces.get_mut(&physics_object) {
             space.import_binary(&mut physics_data.physics_engine, data);
+            space.mark_joints_order_dirty();
         }
     }
 
@@ -335,6 +912,36 @@ impl RapierPhysicsServer {
             .space_flush_queries(&space);
     }
 
+    #[func]
+    /// Get the joints that broke during the last step of the space, because their impulse went over the joint max force.
+    fn space_get_broken_joints(space: Rid) -> Array<Rid> {
+        let physics_data = physics_data();
+        if let Some(space) = physics_data.spaces.get(&space) {
+            return Array::from(space.get_broken_joints().as_slice());
+        }
+        Array::default()
+    }
+
+    #[func]
+    /// Set the callback called with the joint rid for every joint that broke. Called during space_flush_queries.
+    fn space_set_joint_broken_callback(space: Rid, callback: Callable) {
+        let physics_data = physics_data();
+        if let Some(space) = physics_data.spaces.get_mut(&space) {
+            space.set_joint_broken_callback(callback);
+        }
+    }
+
+    #[func]
+    /// Check if the joint broke because its impulse went over the joint max force.
+    fn joint_is_broken(joint: Rid) -> bool {
+        use crate::joints::rapier_joint::IRapierJoint;
+        let physics_data = physics_data();
+        if let Some(joint) = physics_data.joints.get(&joint) {
+            return joint.get_base().is_broken();
+        }
+        false
+    }
+
     #[func]
     /// Get the id of the object by rid. The id can be saved and used when reloading the scene.
     fn get_rapier_id(rid: Rid) -> i64 {
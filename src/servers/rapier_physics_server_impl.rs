@@ -15,27 +15,32 @@ use super::rapier_physics_singleton::get_id_rid;
 use super::rapier_physics_singleton::insert_id_rid;
 use super::rapier_physics_singleton::physics_data;
 use super::rapier_physics_singleton::remove_id_rid;
+use super::rapier_physics_singleton::PhysicsData;
 use super::rapier_physics_singleton::RapierId;
 use super::rapier_project_settings::RapierProjectSettings;
 use crate::bodies::rapier_area::AreaUpdateMode;
 use crate::bodies::rapier_area::RapierArea;
+use crate::bodies::rapier_body::BodyCcdMode;
 use crate::bodies::rapier_body::RapierBody;
 use crate::bodies::rapier_collision_object::IRapierCollisionObject;
 use crate::bodies::rapier_collision_object::RapierCollisionObject;
 #[cfg(feature = "dim2")]
 use crate::joints::rapier_damped_spring_joint_2d::RapierDampedSpringJoint2D;
 use crate::joints::rapier_empty_joint::RapierEmptyJoint;
+use crate::joints::rapier_fixed_joint::RapierFixedJoint;
 #[cfg(feature = "dim2")]
 use crate::joints::rapier_groove_joint_2d::RapierGrooveJoint2D;
 use crate::joints::rapier_joint::IRapierJoint;
 use crate::joints::rapier_joint::RapierJoint;
 use crate::joints::rapier_revolute_joint::RapierRevoluteJoint;
+use crate::joints::rapier_rope_joint::RapierRopeJoint;
 #[cfg(feature = "dim3")]
 use crate::joints::rapier_spherical_joint_3d::RapierSphericalJoint3D;
 use crate::rapier_wrapper::prelude::*;
 use crate::shapes::rapier_capsule_shape::RapierCapsuleShape;
 use crate::shapes::rapier_circle_shape::RapierCircleShape;
 use crate::shapes::rapier_concave_polygon_shape::RapierConcavePolygonShape;
+use crate::shapes::rapier_convex_decomposition_shape::RapierConvexDecompositionShape;
 use crate::shapes::rapier_convex_polygon_shape::RapierConvexPolygonShape;
 #[cfg(feature = "dim3")]
 use crate::shapes::rapier_cylinder_shape_3d::RapierCylinderShape3D;
@@ -77,6 +82,17 @@ impl RapierPhysicsServerImpl {
         self.id
     }
 
+    // A new joint ends up anywhere in the solver order, so the joints of its space are sorted by priority again.
+    fn insert_joint(rid: Rid, joint: RapierJoint, physics_data: &mut PhysicsData) {
+        if let Some(space) = physics_data
+            .spaces
+            .get_mut(&joint.get_base().get_space(&physics_data.ids))
+        {
+            space.mark_joints_order_dirty();
+        }
+        physics_data.joints.insert(rid, joint);
+    }
+
     pub(super) fn default() -> Self {
         Self {
             id: RapierId::default(),
@@ -202,6 +218,15 @@ impl RapierPhysicsServerImpl {
         rid
     }
 
+    pub(super) fn convex_decomposition_shape_create(&mut self) -> Rid {
+        let physics_data = physics_data();
+        let rid = rid_from_int64(rid_allocate_id());
+        let id = self.next_id();
+        RapierConvexDecompositionShape::create(id, rid, &mut physics_data.shapes);
+        insert_id_rid(id, rid, &mut physics_data.ids);
+        rid
+    }
+
     #[cfg(feature = "dim3")]
     pub(super) fn heightmap_shape_create(&mut self) -> Rid {
         let physics_data = physics_data();
@@ -239,10 +264,36 @@ impl RapierPhysicsServerImpl {
     }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn shape_set_margin(&mut self, _shape: Rid, _margin: real) {}
+    pub(super) fn shape_set_margin(&mut self, shape: Rid, margin: real) {
+        let physics_data = physics_data();
+        let margin = margin.max(0.0);
+        let Some(shape) = physics_data.shapes.get_mut(&shape) else {
+            godot_error!("Invalid shape id");
+            return;
+        };
+        if shape.get_base().get_margin() == margin {
+            return;
+        }
+        shape.get_mut_base().set_margin(margin);
+        let shape_id = shape.get_base().get_id();
+        let owners = shape.get_base().get_owners().clone();
+        for owner in owners.keys() {
+            if let Some(owner) = physics_data
+                .collision_objects
+                .get_mut(&get_id_rid(*owner, &physics_data.ids))
+            {
+                owner.get_mut_base().set_shapes_margin(shape_id, margin);
+            }
+        }
+        RapierShapeBase::call_shape_changed(owners, shape_id, physics_data);
+    }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn shape_get_margin(&self, _shape: Rid) -> real {
+    pub(super) fn shape_get_margin(&self, shape: Rid) -> real {
+        let physics_data = physics_data();
+        if let Some(shape) = physics_data.shapes.get(&shape) {
+            return shape.get_base().get_margin();
+        }
         0.0
     }
 
@@ -342,9 +393,40 @@ impl RapierPhysicsServerImpl {
         false
     }
 
-    pub(super) fn space_set_param(&mut self, _space: Rid, _param: SpaceParameter, _value: f32) {}
+    pub(super) fn space_set_param(&mut self, space: Rid, param: SpaceParameter, value: f32) {
+        let physics_data = physics_data();
+        let Some(space) = physics_data.spaces.get_mut(&space) else {
+            return;
+        };
+        space.set_param(param, value);
+        if param != SpaceParameter::BODY_LINEAR_VELOCITY_SLEEP_THRESHOLD
+            && param != SpaceParameter::BODY_ANGULAR_VELOCITY_SLEEP_THRESHOLD
+            && param != SpaceParameter::BODY_TIME_TO_SLEEP
+        {
+            return;
+        }
+        let space_id = space.get_state().get_id();
+        let (angular_threshold, linear_threshold, time_until_sleep) = space.get_body_activation();
+        let linear_threshold = linear_threshold / RapierProjectSettings::get_length_unit();
+        for collision_object in physics_data.collision_objects.values_mut() {
+            if collision_object.get_base().get_space_id() == space_id
+                && let Some(body) = collision_object.get_mut_body()
+            {
+                body.set_sleep_thresholds(
+                    angular_threshold,
+                    linear_threshold,
+                    time_until_sleep,
+                    &mut physics_data.physics_engine,
+                );
+            }
+        }
+    }
 
-    pub(super) fn space_get_param(&self, _space: Rid, _param: SpaceParameter) -> f32 {
+    pub(super) fn space_get_param(&self, space: Rid, param: SpaceParameter) -> f32 {
+        let physics_data = physics_data();
+        if let Some(space) = physics_data.spaces.get(&space) {
+            return space.get_param(param);
+        }
         0.0
     }
 
@@ -1035,8 +1117,14 @@ impl RapierPhysicsServerImpl {
         let physics_data = physics_data();
         if let Some(body) = physics_data.collision_objects.get_mut(&body) {
             if let Some(body) = body.get_mut_body() {
+                // Cast ray is the cheaper mode, so it maps to soft ccd.
+                let mode = match mode {
+                    CcdMode::CAST_RAY => BodyCcdMode::Soft,
+                    CcdMode::CAST_SHAPE => BodyCcdMode::Hard,
+                    _ => BodyCcdMode::Disabled,
+                };
                 body.set_continuous_collision_detection_mode(
-                    mode != CcdMode::DISABLED,
+                    mode,
                     &mut physics_data.physics_engine,
                 );
             }
@@ -1048,9 +1136,11 @@ impl RapierPhysicsServerImpl {
         let physics_data = physics_data();
         if let Some(body) = physics_data.collision_objects.get(&body) {
             if let Some(body) = body.get_body() {
-                if body.get_continuous_collision_detection_mode() {
-                    return CcdMode::CAST_RAY;
-                }
+                return match body.get_continuous_collision_detection_mode() {
+                    BodyCcdMode::Disabled => CcdMode::DISABLED,
+                    BodyCcdMode::Soft => CcdMode::CAST_RAY,
+                    BodyCcdMode::Hard => CcdMode::CAST_SHAPE,
+                };
             }
         }
         CcdMode::DISABLED
@@ -1061,8 +1151,13 @@ impl RapierPhysicsServerImpl {
         let physics_data = physics_data();
         if let Some(body) = physics_data.collision_objects.get_mut(&body) {
             if let Some(body) = body.get_mut_body() {
+                let mode = if enable {
+                    BodyCcdMode::Hard
+                } else {
+                    BodyCcdMode::Disabled
+                };
                 body.set_continuous_collision_detection_mode(
-                    enable,
+                    mode,
                     &mut physics_data.physics_engine,
                 );
             }
@@ -1074,9 +1169,7 @@ impl RapierPhysicsServerImpl {
         let physics_data = physics_data();
         if let Some(body) = physics_data.collision_objects.get(&body) {
             if let Some(body) = body.get_body() {
-                if body.get_continuous_collision_detection_mode() {
-                    return true;
-                }
+                return body.get_continuous_collision_detection_mode() != BodyCcdMode::Disabled;
             }
         }
         false
@@ -1114,10 +1207,23 @@ impl RapierPhysicsServerImpl {
         0
     }
 
-    pub(super) fn body_set_collision_priority(&mut self, _body: Rid, _priority: f32) {}
+    pub(super) fn body_set_collision_priority(&mut self, body: Rid, priority: f32) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body)
+            && let Some(body) = body.get_mut_body()
+        {
+            body.set_collision_priority(priority, &mut physics_data.physics_engine);
+        }
+    }
 
-    pub(super) fn body_get_collision_priority(&self, _body: Rid) -> f32 {
-        0.0
+    pub(super) fn body_get_collision_priority(&self, body: Rid) -> f32 {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body)
+            && let Some(body) = body.get_body()
+        {
+            return body.get_collision_priority();
+        }
+        1.0
     }
 
     #[cfg(feature = "dim3")]
@@ -1467,14 +1573,22 @@ impl RapierPhysicsServerImpl {
         0
     }
 
-    pub(super) fn body_set_contacts_reported_depth_threshold(
-        &mut self,
-        _body: Rid,
-        _threshold: f32,
-    ) {
+    pub(super) fn body_set_contacts_reported_depth_threshold(&mut self, body: Rid, threshold: f32) {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get_mut(&body) {
+            if let Some(body) = body.get_mut_body() {
+                body.set_contacts_reported_depth_threshold(threshold);
+            }
+        }
     }
 
-    pub(super) fn body_get_contacts_reported_depth_threshold(&self, _body: Rid) -> f32 {
+    pub(super) fn body_get_contacts_reported_depth_threshold(&self, body: Rid) -> f32 {
+        let physics_data = physics_data();
+        if let Some(body) = physics_data.collision_objects.get(&body) {
+            if let Some(body) = body.get_body() {
+                return body.get_contacts_reported_depth_threshold();
+            }
+        }
         0.0
     }
 
@@ -1658,17 +1772,95 @@ impl RapierPhysicsServerImpl {
         rid
     }
 
+    pub(super) fn joint_make_fixed(
+        &mut self,
+        rid: Rid,
+        body_a: Rid,
+        frame_a: Transform,
+        body_b: Rid,
+        frame_b: Transform,
+    ) {
+        let physics_data = physics_data();
+        let mut joint: RapierJoint;
+        if let Some(body_a) = physics_data.collision_objects.get(&body_a)
+            && let Some(body_b) = physics_data.collision_objects.get(&body_b)
+        {
+            let id = self.next_id();
+            joint = RapierJoint::RapierFixedJoint(RapierFixedJoint::new(
+                id,
+                rid,
+                frame_a,
+                frame_b,
+                body_a,
+                body_b,
+                &mut physics_data.physics_engine,
+            ));
+            if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
+                prev_joint
+                    .get_mut_base()
+                    .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
+            }
+        } else {
+            let id = self.next_id();
+            joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
+        }
+        Self::insert_joint(rid, joint, physics_data);
+    }
+
+    pub(super) fn joint_make_rope(
+        &mut self,
+        rid: Rid,
+        body_a: Rid,
+        anchor_a: Vector,
+        body_b: Rid,
+        anchor_b: Vector,
+        max_length: real,
+    ) {
+        let physics_data = physics_data();
+        let mut joint: RapierJoint;
+        if let Some(body_a) = physics_data.collision_objects.get(&body_a)
+            && let Some(body_b) = physics_data.collision_objects.get(&body_b)
+        {
+            let id = self.next_id();
+            joint = RapierJoint::RapierRopeJoint(RapierRopeJoint::new(
+                id,
+                rid,
+                anchor_a,
+                anchor_b,
+                max_length,
+                body_a,
+                body_b,
+                &mut physics_data.physics_engine,
+            ));
+            if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
+                prev_joint
+                    .get_mut_base()
+                    .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
+            }
+        } else {
+            let id = self.next_id();
+            joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
+        }
+        Self::insert_joint(rid, joint, physics_data);
+    }
+
     pub(super) fn joint_clear(&mut self, rid: Rid) {
         let physics_data = physics_data();
         if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
             let id = self.next_id();
             let mut joint = RapierEmptyJoint::new(id);
-            joint
-                .get_mut_base()
-                .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             prev_joint
                 .get_mut_base()
                 .destroy_joint(&mut physics_data.physics_engine);
+            joint
+                .get_mut_base()
+                .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             physics_data
                 .joints
                 .insert(rid, RapierJoint::RapierEmptyJoint(joint));
@@ -1714,10 +1906,25 @@ impl RapierPhysicsServerImpl {
     }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn joint_set_solver_priority(&mut self, _joint: Rid, _priority: i32) {}
+    pub(super) fn joint_set_solver_priority(&mut self, joint: Rid, priority: i32) {
+        let physics_data = physics_data();
+        if let Some(joint) = physics_data.joints.get_mut(&joint) {
+            joint.get_mut_base().set_solver_priority(priority);
+            if let Some(space) = physics_data
+                .spaces
+                .get_mut(&joint.get_base().get_space(&physics_data.ids))
+            {
+                space.mark_joints_order_dirty();
+            }
+        }
+    }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn joint_get_solver_priority(&self, _joint: Rid) -> i32 {
+    pub(super) fn joint_get_solver_priority(&self, joint: Rid) -> i32 {
+        let physics_data = physics_data();
+        if let Some(joint) = physics_data.joints.get(&joint) {
+            return joint.get_base().get_solver_priority();
+        }
         1
     }
 
@@ -1746,25 +1953,36 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn pin_joint_set_param(&mut self, _joint: Rid, _param: PinJointParam, _value: f32) {}
+    pub(super) fn pin_joint_set_param(&mut self, joint: Rid, param: PinJointParam, value: f32) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierSphericalJoint3D(joint)) =
+            physics_data.joints.get_mut(&joint)
+        {
+            joint.set_param(param, value, &mut physics_data.physics_engine);
+        }
+    }
 
     #[cfg(feature = "dim3")]
-    pub(super) fn pin_joint_get_param(&self, _joint: Rid, _param: PinJointParam) -> f32 {
+    pub(super) fn pin_joint_get_param(&self, joint: Rid, param: PinJointParam) -> f32 {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierSphericalJoint3D(joint)) = physics_data.joints.get(&joint) {
+            return joint.get_param(param);
+        }
         0.0
     }
 
@@ -1831,18 +2049,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
@@ -1873,18 +2091,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
@@ -1947,18 +2165,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
@@ -2006,42 +2224,52 @@ impl RapierPhysicsServerImpl {
             joint = RapierJoint::RapierConeTwistJoint3D(RapierConeTwistJoint3D::new(
                 id,
                 rid,
-                local_ref_a.origin,
-                local_ref_b.origin,
+                local_ref_a,
+                local_ref_b,
                 body_a,
                 body_b,
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn cone_twist_joint_set_param(
         &mut self,
-        _joint: Rid,
-        _param: physics_server_3d::ConeTwistJointParam,
-        _value: f32,
+        joint: Rid,
+        param: physics_server_3d::ConeTwistJointParam,
+        value: f32,
     ) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierConeTwistJoint3D(joint)) =
+            physics_data.joints.get_mut(&joint)
+        {
+            joint.set_param(param, value, &mut physics_data.physics_engine);
+        }
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn cone_twist_joint_get_param(
         &self,
-        _joint: Rid,
-        _param: physics_server_3d::ConeTwistJointParam,
+        joint: Rid,
+        param: physics_server_3d::ConeTwistJointParam,
     ) -> f32 {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierConeTwistJoint3D(joint)) = physics_data.joints.get(&joint) {
+            return joint.get_param(param);
+        }
         0.0
     }
 
@@ -2064,64 +2292,86 @@ impl RapierPhysicsServerImpl {
             joint = RapierJoint::RapierGeneric6DOFJoint3D(RapierGeneric6DOFJoint3D::new(
                 id,
                 rid,
-                local_ref_a.origin,
-                local_ref_b.origin,
+                local_ref_a,
+                local_ref_b,
                 body_a,
                 body_b,
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn generic_6dof_joint_set_param(
         &mut self,
-        _joint: Rid,
-        _axis: Vector3Axis,
-        _param: physics_server_3d::G6dofJointAxisParam,
-        _value: f32,
+        joint: Rid,
+        axis: Vector3Axis,
+        param: physics_server_3d::G6dofJointAxisParam,
+        value: f32,
     ) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierGeneric6DOFJoint3D(joint)) =
+            physics_data.joints.get_mut(&joint)
+        {
+            joint.set_param(axis, param, value, &mut physics_data.physics_engine);
+        }
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn generic_6dof_joint_get_param(
         &self,
-        _joint: Rid,
-        _axis: Vector3Axis,
-        _param: physics_server_3d::G6dofJointAxisParam,
+        joint: Rid,
+        axis: Vector3Axis,
+        param: physics_server_3d::G6dofJointAxisParam,
     ) -> f32 {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierGeneric6DOFJoint3D(joint)) = physics_data.joints.get(&joint)
+        {
+            return joint.get_param(axis, param);
+        }
         0.0
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn generic_6dof_joint_set_flag(
         &mut self,
-        _joint: Rid,
-        _axis: Vector3Axis,
-        _flag: physics_server_3d::G6dofJointAxisFlag,
-        _enable: bool,
+        joint: Rid,
+        axis: Vector3Axis,
+        flag: physics_server_3d::G6dofJointAxisFlag,
+        enable: bool,
     ) {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierGeneric6DOFJoint3D(joint)) =
+            physics_data.joints.get_mut(&joint)
+        {
+            joint.set_flag(axis, flag, enable, &mut physics_data.physics_engine);
+        }
     }
 
     #[cfg(feature = "dim3")]
     pub(super) fn generic_6dof_joint_get_flag(
         &self,
-        _joint: Rid,
-        _axis: Vector3Axis,
-        _flag: physics_server_3d::G6dofJointAxisFlag,
+        joint: Rid,
+        axis: Vector3Axis,
+        flag: physics_server_3d::G6dofJointAxisFlag,
     ) -> bool {
+        let physics_data = physics_data();
+        if let Some(RapierJoint::RapierGeneric6DOFJoint3D(joint)) = physics_data.joints.get(&joint)
+        {
+            return joint.get_flag(axis, flag);
+        }
         false
     }
 
@@ -2143,18 +2393,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim2")]
@@ -2184,18 +2434,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim2")]
@@ -2223,18 +2473,18 @@ impl RapierPhysicsServerImpl {
                 &mut physics_data.physics_engine,
             ));
             if let Some(mut prev_joint) = physics_data.joints.remove(&rid) {
-                joint
-                    .get_mut_base()
-                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
                 prev_joint
                     .get_mut_base()
                     .destroy_joint(&mut physics_data.physics_engine);
+                joint
+                    .get_mut_base()
+                    .copy_settings_from(prev_joint.get_base(), &mut physics_data.physics_engine);
             }
         } else {
             let id = self.next_id();
             joint = RapierJoint::RapierEmptyJoint(RapierEmptyJoint::new(id));
         }
-        physics_data.joints.insert(rid, joint);
+        Self::insert_joint(rid, joint, physics_data);
     }
 
     #[cfg(feature = "dim2")]
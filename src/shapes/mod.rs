@@ -1,6 +1,7 @@
 pub mod rapier_capsule_shape;
 pub mod rapier_circle_shape;
 pub mod rapier_concave_polygon_shape;
+pub mod rapier_convex_decomposition_shape;
 pub mod rapier_convex_polygon_shape;
 #[cfg(feature = "dim3")]
 pub mod rapier_cylinder_shape_3d;
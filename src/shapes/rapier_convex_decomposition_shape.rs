@@ -0,0 +1,231 @@
+#[cfg(feature = "dim2")]
+use godot::classes::physics_server_2d::*;
+#[cfg(feature = "dim3")]
+use godot::classes::physics_server_3d::*;
+use godot::prelude::*;
+use rapier::parry::transformation::vhacd::VHACDParameters;
+
+use super::rapier_shape::RapierShape;
+use crate::rapier_wrapper::prelude::*;
+use crate::servers::rapier_physics_singleton::PhysicsShapes;
+use crate::servers::rapier_physics_singleton::RapierId;
+use crate::shapes::rapier_shape::*;
+use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::types::*;
+#[cfg(feature = "dim2")]
+const POINTS_KEY: &str = "segments";
+#[cfg(feature = "dim3")]
+const POINTS_KEY: &str = "faces";
+// Concave mesh decomposed in convex hulls (VHACD), so it can be used on dynamic bodies.
+// Takes the same points as the concave polygon shape, in a dictionary along with the decomposition parameters.
+pub struct RapierConvexDecompositionShape {
+    points: PackedVectorArray,
+    params: VHACDParameters,
+    base: RapierShapeBase,
+}
+impl RapierConvexDecompositionShape {
+    pub fn create(id: RapierId, rid: Rid, physics_shapes: &mut PhysicsShapes) {
+        let shape = Self {
+            points: PackedVectorArray::new(),
+            params: VHACDParameters::default(),
+            base: RapierShapeBase::new(id, rid),
+        };
+        physics_shapes.insert(rid, RapierShape::RapierConvexDecompositionShape(shape));
+    }
+}
+impl RapierConvexDecompositionShape {
+    fn create_rapier_shape(
+        points: &PackedVectorArray,
+        params: &VHACDParameters,
+        handle: ShapeHandle,
+        physics_engine: &mut PhysicsEngine,
+    ) -> bool {
+        let point_count = points.len();
+        let mut rapier_points = Vec::with_capacity(point_count);
+        for point in points.as_slice() {
+            rapier_points.push(vector_to_rapier(*point));
+        }
+        let mut indices = Vec::new();
+        #[cfg(feature = "dim2")]
+        for i in (0..point_count).step_by(2) {
+            indices.push([i as u32, (i + 1) as u32]);
+        }
+        #[cfg(feature = "dim3")]
+        for i in (0..point_count).step_by(3) {
+            indices.push([i as u32, (i + 1) as u32, (i + 2) as u32]);
+        }
+        physics_engine.shape_create_convex_decomposition(&rapier_points, &indices, params, handle)
+    }
+}
+impl IRapierShape for RapierConvexDecompositionShape {
+    fn get_base(&self) -> &RapierShapeBase {
+        &self.base
+    }
+
+    fn get_mut_base(&mut self) -> &mut RapierShapeBase {
+        &mut self.base
+    }
+
+    fn get_type(&self) -> ShapeType {
+        ShapeType::CUSTOM
+    }
+
+    fn allows_one_way_collision(&self) -> bool {
+        true
+    }
+
+    fn set_data(&mut self, data: Variant, physics_engine: &mut PhysicsEngine) {
+        let Ok(dictionary) = data.try_to::<Dictionary>() else {
+            godot_error!("ConvexDecomposition data must be a Dictionary");
+            return;
+        };
+        let Some(points) = dictionary
+            .get(POINTS_KEY)
+            .and_then(|points| points.try_to::<PackedVectorArray>().ok())
+        else {
+            godot_error!(
+                "ConvexDecomposition data must contain a {} array",
+                POINTS_KEY
+            );
+            return;
+        };
+        #[cfg(feature = "dim2")]
+        if points.is_empty() || points.len() % 2 != 0 {
+            godot_error!("ConvexDecomposition must have an even number of points");
+            return;
+        }
+        #[cfg(feature = "dim3")]
+        if points.is_empty() || points.len() % 3 != 0 {
+            godot_error!("ConvexDecomposition must have a multiple of 3 number of points");
+            return;
+        }
+        let mut params = VHACDParameters::default();
+        if let Some(resolution) = dictionary.get("resolution") {
+            params.resolution = resolution.try_to::<i32>().unwrap_or(1).max(1) as u32;
+        }
+        if let Some(concavity) = dictionary.get("concavity") {
+            params.concavity = variant_to_float(&concavity).max(0.0);
+        }
+        if let Some(max_convex_hulls) = dictionary.get("max_convex_hulls") {
+            params.max_convex_hulls = max_convex_hulls.try_to::<i32>().unwrap_or(1).max(1) as u32;
+        }
+        if !Self::create_rapier_shape(&points, &params, self.base.get_id(), physics_engine) {
+            godot_error!(
+                "ConvexDecomposition could not build any convex hull, the points must not be flat"
+            );
+            return;
+        }
+        self.points = points;
+        self.params = params;
+        self.base.reset_aabb(physics_engine);
+    }
+
+    fn get_data(&self, _physics_engine: &PhysicsEngine) -> Variant {
+        let mut dictionary = Dictionary::new();
+        dictionary.set(POINTS_KEY, self.points.clone());
+        dictionary.set("resolution", self.params.resolution as i32);
+        dictionary.set("concavity", self.params.concavity);
+        dictionary.set("max_convex_hulls", self.params.max_convex_hulls as i32);
+        dictionary.to_variant()
+    }
+}
+#[cfg(feature = "test")]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::servers::rapier_physics_singleton::physics_data;
+    use crate::servers::rapier_physics_singleton::PhysicsShapes;
+    use crate::shapes::rapier_shape::IRapierShape;
+    #[derive(GodotClass)]
+    #[class(base=Object, init)]
+    pub struct RapierConvexDecompositionShapeTests {}
+    #[godot_api]
+    impl RapierConvexDecompositionShapeTests {
+        #[func]
+        fn test_create() {
+            let mut physics_shapes = PhysicsShapes::new();
+            let rid = Rid::new(123);
+            RapierConvexDecompositionShape::create(0, rid, &mut physics_shapes);
+            assert!(physics_shapes.contains_key(&rid));
+            match physics_shapes.get(&rid) {
+                Some(RapierShape::RapierConvexDecompositionShape(_)) => {}
+                _ => panic!("Shape was not inserted correctly"),
+            }
+            let decomposition_shape = physics_shapes.get(&rid).unwrap();
+            assert_eq!(decomposition_shape.get_type(), ShapeType::CUSTOM);
+        }
+
+        #[func]
+        fn test_set_data() {
+            let mut decomposition_shape = RapierConvexDecompositionShape {
+                points: PackedVectorArray::new(),
+                params: VHACDParameters::default(),
+                base: RapierShapeBase::new(RapierId::default(), Rid::Invalid),
+            };
+            #[cfg(feature = "dim2")]
+            let arr = PackedVectorArray::from(vec![
+                Vector::new(0.0, 0.0),
+                Vector::new(1.0, 0.0),
+                Vector::new(1.0, 0.0),
+                Vector::new(1.0, 1.0),
+                Vector::new(1.0, 1.0),
+                Vector::new(0.0, 0.0),
+            ]);
+            #[cfg(feature = "dim3")]
+            let arr = PackedVectorArray::from(vec![
+                Vector::new(0.0, 0.0, 0.0),
+                Vector::new(1.0, 0.0, 0.0),
+                Vector::new(0.0, 1.0, 0.0),
+                Vector::new(0.0, 0.0, 0.0),
+                Vector::new(0.0, 1.0, 0.0),
+                Vector::new(0.0, 0.0, 1.0),
+                Vector::new(0.0, 0.0, 0.0),
+                Vector::new(0.0, 0.0, 1.0),
+                Vector::new(1.0, 0.0, 0.0),
+                Vector::new(1.0, 0.0, 0.0),
+                Vector::new(0.0, 0.0, 1.0),
+                Vector::new(0.0, 1.0, 0.0),
+            ]);
+            let mut dict = Dictionary::new();
+            let _ = dict.insert(POINTS_KEY, arr.clone());
+            let _ = dict.insert("max_convex_hulls", 4);
+            decomposition_shape.set_data(dict.to_variant(), &mut physics_data().physics_engine);
+            let data: Dictionary = decomposition_shape
+                .get_data(&physics_data().physics_engine)
+                .try_to()
+                .unwrap();
+            let points: PackedVectorArray = data.get(POINTS_KEY).unwrap().try_to().unwrap();
+            assert_eq!(points, arr);
+            let max_convex_hulls: i32 = data.get("max_convex_hulls").unwrap().try_to().unwrap();
+            assert_eq!(max_convex_hulls, 4);
+            // flat points are rejected and the previous data is kept
+            #[cfg(feature = "dim2")]
+            let flat_arr = PackedVectorArray::from(vec![
+                Vector::new(0.0, 0.0),
+                Vector::new(1.0, 0.0),
+                Vector::new(1.0, 0.0),
+                Vector::new(2.0, 0.0),
+            ]);
+            #[cfg(feature = "dim3")]
+            let flat_arr = PackedVectorArray::from(vec![
+                Vector::new(0.0, 0.0, 0.0),
+                Vector::new(1.0, 0.0, 0.0),
+                Vector::new(0.0, 0.0, 1.0),
+            ]);
+            let mut flat_dict = Dictionary::new();
+            let _ = flat_dict.insert(POINTS_KEY, flat_arr);
+            decomposition_shape
+                .set_data(flat_dict.to_variant(), &mut physics_data().physics_engine);
+            let data: Dictionary = decomposition_shape
+                .get_data(&physics_data().physics_engine)
+                .try_to()
+                .unwrap();
+            let points: PackedVectorArray = data.get(POINTS_KEY).unwrap().try_to().unwrap();
+            assert_eq!(points, arr);
+            decomposition_shape
+                .get_mut_base()
+                .destroy_shape(&mut physics_data().physics_engine);
+        }
+    }
+}
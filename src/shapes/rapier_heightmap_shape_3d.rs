@@ -1,7 +1,9 @@
+use godot::classes::image::Format;
 #[cfg(feature = "dim2")]
 use godot::classes::physics_server_2d::*;
 #[cfg(feature = "dim3")]
 use godot::classes::physics_server_3d::*;
+use godot::classes::Image;
 use godot::prelude::*;
 
 use super::rapier_shape::RapierShape;
@@ -11,16 +13,49 @@ use crate::servers::rapier_physics_singleton::RapierId;
 use crate::shapes::rapier_shape::IRapierShape;
 use crate::shapes::rapier_shape_base::RapierShapeBase;
 use crate::types::*;
+// Heights can be given as a float array (with width and depth) or as an image with a single channel.
+// NaN heights and the optional holes mask (one byte per cell) remove cells from the heightfield.
 pub struct RapierHeightMapShape3D {
+    heights: PackedFloatArray,
+    width: i32,
+    depth: i32,
+    cell_scale: Vector2,
+    fix_internal_edges: bool,
+    holes: PackedByteArray,
     base: RapierShapeBase,
 }
 impl RapierHeightMapShape3D {
     pub fn create(id: RapierId, rid: Rid, physics_shapes: &mut PhysicsShapes) {
         let shape = Self {
+            heights: PackedFloatArray::new(),
+            width: 0,
+            depth: 0,
+            cell_scale: Vector2::ONE,
+            fix_internal_edges: true,
+            holes: PackedByteArray::new(),
             base: RapierShapeBase::new(id, rid),
         };
         physics_shapes.insert(rid, RapierShape::RapierHeightMapShape3D(shape));
     }
+
+    fn image_to_heights(image: &Gd<Image>) -> Option<PackedFloatArray> {
+        if image.is_compressed() {
+            godot_error!("Heightmap image must not be compressed");
+            return None;
+        }
+        let format = image.get_format();
+        if format != Format::RF && format != Format::RH && format != Format::R8 {
+            godot_error!("Heightmap image must be in R8, RF or RH format");
+            return None;
+        }
+        let mut heights = PackedFloatArray::new();
+        for y in 0..image.get_height() {
+            for x in 0..image.get_width() {
+                heights.push(image.get_pixel(x, y).r as real);
+            }
+        }
+        Some(heights)
+    }
 }
 impl IRapierShape for RapierHeightMapShape3D {
     fn get_base(&self) -> &RapierShapeBase {
@@ -43,49 +78,71 @@ impl IRapierShape for RapierHeightMapShape3D {
         let width;
         let depth;
         let heights;
+        let mut cell_scale = Vector2::ONE;
+        let fix_internal_edges;
+        let holes;
         if let Ok(dictionary) = data.try_to::<Dictionary>() {
-            let in_width = dictionary.get_or_nil("width");
-            let in_depth = dictionary.get_or_nil("depth");
             let new_heights = dictionary.get_or_nil("heights");
-            if let Ok(in_width) = in_width.try_to::<i32>()
-                && let Ok(in_depth) = in_depth.try_to::<i32>()
+            if let Ok(image) = new_heights.try_to::<Gd<Image>>() {
+                let Some(image_heights) = Self::image_to_heights(&image) else {
+                    return;
+                };
+                width = image.get_width();
+                depth = image.get_height();
+                heights = image_heights;
+            } else if let Ok(in_width) = dictionary.get_or_nil("width").try_to::<i32>()
+                && let Ok(in_depth) = dictionary.get_or_nil("depth").try_to::<i32>()
                 && let Ok(in_heights) = new_heights.try_to::<PackedFloatArray>()
             {
                 width = in_width;
                 depth = in_depth;
                 heights = in_heights;
-                // else if let Ok(image) = heights.try_to::<Object>() {
-                // TODO image support
-                //}
-                // Compute min and max heights or use precomputed values.
-                let mut min_height: real = 0.0;
-                let mut max_height: real = 0.0;
-                if let Some(new_min_height) = dictionary.get("min_height")
-                    && let Some(new_max_height) = dictionary.get("max_height")
-                {
-                    let new_min_height = variant_to_float(&new_min_height);
-                    let new_max_height = variant_to_float(&new_max_height);
-                    min_height = new_min_height;
-                    max_height = new_max_height;
-                } else {
-                    let heights_size = heights.len();
-                    for i in 0..heights_size {
-                        let h = heights[i];
-                        if h < min_height {
-                            min_height = h;
-                        } else if h > max_height {
-                            max_height = h;
-                        }
+            } else {
+                godot_error!("Invalid heightmap dictionary data. Got {}", data);
+                return;
+            }
+            // Compute min and max heights or use precomputed values.
+            let mut min_height: real = 0.0;
+            let mut max_height: real = 0.0;
+            if let Some(new_min_height) = dictionary.get("min_height")
+                && let Some(new_max_height) = dictionary.get("max_height")
+            {
+                let new_min_height = variant_to_float(&new_min_height);
+                let new_max_height = variant_to_float(&new_max_height);
+                min_height = new_min_height;
+                max_height = new_max_height;
+            } else {
+                for h in heights.as_slice() {
+                    if h.is_nan() {
+                        continue;
                     }
+                    min_height = min_height.min(*h);
+                    max_height = max_height.max(*h);
                 }
-                if min_height > max_height {
-                    godot_error!("Invalid heightmap shape data");
+            }
+            if min_height > max_height {
+                godot_error!("Invalid heightmap shape data");
+                return;
+            }
+            if let Some(new_cell_scale) = dictionary.get("cell_scale") {
+                if let Ok(new_cell_scale) = new_cell_scale.try_to::<Vector2>()
+                    && new_cell_scale.x > 0.0
+                    && new_cell_scale.y > 0.0
+                {
+                    cell_scale = new_cell_scale;
+                } else {
+                    godot_error!("Heightmap cell_scale must be a positive Vector2");
                     return;
                 }
-            } else {
-                godot_error!("Invalid heightmap dictionary data. Got {}", data);
-                return;
             }
+            fix_internal_edges = dictionary
+                .get("fix_internal_edges")
+                .and_then(|value| value.try_to::<bool>().ok())
+                .unwrap_or(true);
+            holes = dictionary
+                .get("holes")
+                .and_then(|value| value.try_to::<PackedByteArray>().ok())
+                .unwrap_or_default();
         } else {
             godot_error!("Invalid heightmap shape data type. Got {}", data);
             return;
@@ -98,20 +155,39 @@ impl IRapierShape for RapierHeightMapShape3D {
             godot_error!("Heightmap must have width and depth at least 2");
             return;
         }
-        physics_engine.shape_create_heightmap(heights.as_slice(), width, depth, self.base.get_id());
+        if !holes.is_empty() && holes.len() != ((width - 1) * (depth - 1)) as usize {
+            godot_error!("Heightmap holes must have one value per cell");
+            return;
+        }
+        let cell_holes: Vec<bool> = holes.as_slice().iter().map(|hole| *hole != 0).collect();
+        physics_engine.shape_create_heightmap(
+            heights.as_slice(),
+            width,
+            depth,
+            vector_to_rapier(Vector::new(cell_scale.x, 1.0, cell_scale.y)),
+            fix_internal_edges,
+            &cell_holes,
+            self.base.get_id(),
+        );
+        self.heights = heights;
+        self.width = width;
+        self.depth = depth;
+        self.cell_scale = cell_scale;
+        self.fix_internal_edges = fix_internal_edges;
+        self.holes = holes;
         self.base.reset_aabb(physics_engine);
     }
 
-    fn get_data(&self, physics_engine: &PhysicsEngine) -> Variant {
+    fn get_data(&self, _physics_engine: &PhysicsEngine) -> Variant {
         let mut dictionary = Dictionary::new();
-        let (heights, depth, width) = physics_engine.shape_get_heightmap(self.base.get_id());
-        let _ = dictionary.insert("width", width);
-        let _ = dictionary.insert("depth", depth);
-        let mut packed_heights = PackedFloatArray::default();
-        for h in heights.iter() {
-            packed_heights.push(*h)
+        let _ = dictionary.insert("width", self.width);
+        let _ = dictionary.insert("depth", self.depth);
+        let _ = dictionary.insert("heights", self.heights.clone());
+        let _ = dictionary.insert("cell_scale", self.cell_scale);
+        let _ = dictionary.insert("fix_internal_edges", self.fix_internal_edges);
+        if !self.holes.is_empty() {
+            let _ = dictionary.insert("holes", self.holes.clone());
         }
-        let _ = dictionary.insert("heights", packed_heights);
         dictionary.to_variant()
     }
 }
@@ -60,3 +60,57 @@ impl IRapierShape for RapierRectangleShape {
         vector_to_godot(half_extents).to_variant()
     }
 }
+#[cfg(all(feature = "test", feature = "dim3"))]
+mod tests {
+    use godot::prelude::*;
+
+    use super::*;
+    use crate::servers::rapier_physics_singleton::physics_data;
+    #[derive(GodotClass)]
+    #[class(base=Object, init)]
+    pub struct RapierBoxShape3DTests {}
+    #[godot_api]
+    impl RapierBoxShape3DTests {
+        #[func]
+        fn test_skewed_hull() {
+            let mut box_shape = RapierRectangleShape {
+                base: RapierShapeBase::new(RapierId::default(), Rid::Invalid),
+            };
+            let half_extents = Vector3::new(1.0, 2.0, 3.0);
+            box_shape.set_data(
+                half_extents.to_variant(),
+                &mut physics_data().physics_engine,
+            );
+            // rotated, scaled and sheared basis
+            let basis = Basis::from_euler(EulerOrder::XYZ, Vector3::new(0.3, 0.2, 0.1))
+                * Basis::from_cols(
+                    Vector3::new(1.0, 0.5, 0.0),
+                    Vector3::new(0.0, 2.0, 0.0),
+                    Vector3::new(0.25, 0.0, 1.5),
+                );
+            let transform = Transform3D::new(basis, Vector3::new(1.0, 2.0, 3.0));
+            let shape_info = shape_info_from_body_shape(box_shape.get_base().get_id(), transform);
+            let shape = physics_data()
+                .physics_engine
+                .get_scaled_shape(shape_info)
+                .unwrap();
+            let hull = shape.as_convex_polyhedron().unwrap();
+            assert_eq!(hull.points().len(), 8);
+            for x in [-1.0, 1.0] {
+                for y in [-1.0, 1.0] {
+                    for z in [-1.0, 1.0] {
+                        let corner = half_extents * Vector3::new(x, y, z);
+                        let expected = transform * corner;
+                        assert!(hull.points().iter().any(|point| {
+                            let point = shape_info.transform * point;
+                            (vector_to_godot(point.coords) - expected).length() < 1e-3
+                        }));
+                    }
+                }
+            }
+            box_shape
+                .get_mut_base()
+                .destroy_shape(&mut physics_data().physics_engine);
+        }
+    }
+}
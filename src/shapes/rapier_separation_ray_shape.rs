@@ -10,6 +10,7 @@ use crate::servers::rapier_physics_singleton::PhysicsShapes;
 use crate::servers::rapier_physics_singleton::RapierId;
 use crate::shapes::rapier_shape::IRapierShape;
 use crate::shapes::rapier_shape_base::RapierShapeBase;
+use crate::types::*;
 pub struct RapierSeparationRayShape {
     length: f32,
     slide_on_slope: bool,
@@ -24,6 +25,14 @@ impl RapierSeparationRayShape {
         };
         physics_shapes.insert(rid, RapierShape::RapierSeparationRayShape(shape));
     }
+
+    pub fn get_length(&self) -> f32 {
+        self.length
+    }
+
+    pub fn get_slide_on_slope(&self) -> bool {
+        self.slide_on_slope
+    }
 }
 impl IRapierShape for RapierSeparationRayShape {
     fn get_base(&self) -> &RapierShapeBase {
@@ -42,7 +51,7 @@ impl IRapierShape for RapierSeparationRayShape {
         false
     }
 
-    fn set_data(&mut self, data: Variant, _physics_engine: &mut PhysicsEngine) {
+    fn set_data(&mut self, data: Variant, physics_engine: &mut PhysicsEngine) {
         if data.get_type() != VariantType::DICTIONARY {
             godot_error!(
                 "RapierSeparationRayShape data must be a dictionary. Got {}",
@@ -60,6 +69,17 @@ impl IRapierShape for RapierSeparationRayShape {
             .get_or_nil("slide_on_slope")
             .try_to()
             .unwrap_or_default();
+        // The ray points along the local Y axis in 2D and the local Z axis in 3D.
+        #[cfg(feature = "dim2")]
+        let end = Vector2::new(0.0, self.length);
+        #[cfg(feature = "dim3")]
+        let end = Vector3::new(0.0, 0.0, self.length);
+        physics_engine.shape_create_segment(
+            vector_to_rapier(Vector::ZERO),
+            vector_to_rapier(end),
+            self.base.get_id(),
+        );
+        self.base.reset_aabb(physics_engine);
     }
 
     fn get_data(&self, _physics_engine: &PhysicsEngine) -> Variant {
@@ -7,6 +7,7 @@ use godot::prelude::*;
 use super::rapier_capsule_shape::RapierCapsuleShape;
 use super::rapier_circle_shape::RapierCircleShape;
 use super::rapier_concave_polygon_shape::RapierConcavePolygonShape;
+use super::rapier_convex_decomposition_shape::RapierConvexDecompositionShape;
 use super::rapier_convex_polygon_shape::RapierConvexPolygonShape;
 #[cfg(feature = "dim3")]
 use super::rapier_cylinder_shape_3d::RapierCylinderShape3D;
@@ -31,6 +32,7 @@ pub enum RapierShape {
     RapierCapsuleShape(RapierCapsuleShape),
     RapierCircleShape(RapierCircleShape),
     RapierConcavePolygonShape(RapierConcavePolygonShape),
+    RapierConvexDecompositionShape(RapierConvexDecompositionShape),
     RapierConvexPolygonShape(RapierConvexPolygonShape),
     #[cfg(feature = "dim3")]
     RapierCylinderShape3D(RapierCylinderShape3D),
@@ -89,6 +91,7 @@ impl_rapier_shape_trait!(
     RapierCapsuleShape,
     RapierCircleShape,
     RapierConcavePolygonShape,
+    RapierConvexDecompositionShape,
     RapierConvexPolygonShape,
     RapierCylinderShape3D,
     RapierHeightMapShape3D,
@@ -102,6 +105,7 @@ impl_rapier_shape_trait!(
     RapierCapsuleShape,
     RapierCircleShape,
     RapierConcavePolygonShape,
+    RapierConvexDecompositionShape,
     RapierConvexPolygonShape,
     RapierSegmentShape2D,
     RapierRectangleShape,
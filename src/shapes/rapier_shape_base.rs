@@ -34,6 +34,8 @@ pub struct RapierShapeState {
     )]
     owners: HashMap<RapierId, i32>,
     id: RapierId,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    margin: real,
 }
 pub struct RapierShapeBase {
     rid: Rid,
@@ -118,6 +120,14 @@ impl RapierShapeBase {
         self.state.id
     }
 
+    pub fn set_margin(&mut self, margin: real) {
+        self.state.margin = margin;
+    }
+
+    pub fn get_margin(&self) -> real {
+        self.state.margin
+    }
+
     pub fn destroy_shape(&mut self, physics_engine: &mut PhysicsEngine) {
         physics_engine.shape_destroy(self.get_id());
     }
@@ -196,7 +196,8 @@ impl RapierDirectSpaceStateImpl {
         let Some(space) = physics_data.spaces.get(&self.space) else {
             return 0;
         };
-        let shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        let mut shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        shape_info.pixel_margin = shape.get_base().get_margin();
         let mut query_excluded_info = QueryExcludedInfo {
             query_collision_layer_mask: collision_mask,
             ..Default::default()
@@ -274,7 +275,8 @@ impl RapierDirectSpaceStateImpl {
             return false;
         };
         let rapier_motion = vector_to_rapier(motion);
-        let shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        let mut shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        shape_info.pixel_margin = shape.get_base().get_margin();
         let query_excluded_info = QueryExcludedInfo {
             query_collision_layer_mask: collision_mask,
             ..Default::default()
@@ -323,7 +325,8 @@ impl RapierDirectSpaceStateImpl {
             return false;
         };
         let results_out = results as *mut Vector;
-        let shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        let mut shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        shape_info.pixel_margin = shape.get_base().get_margin();
         let mut query_excluded_info = QueryExcludedInfo {
             query_collision_layer_mask: collision_mask,
             ..Default::default()
@@ -385,7 +388,8 @@ impl RapierDirectSpaceStateImpl {
             return false;
         };
         let rapier_motion = vector_to_rapier(motion);
-        let shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        let mut shape_info = shape_info_from_body_shape(shape.get_base().get_id(), transform);
+        shape_info.pixel_margin = shape.get_base().get_margin();
         let query_excluded_info = QueryExcludedInfo {
             query_collision_layer_mask: collision_mask,
             ..Default::default()
@@ -1,5 +1,6 @@
 use bodies::rapier_area::RapierArea;
 use bodies::rapier_body::RapierBody;
+use godot::builtin::real_consts;
 #[cfg(feature = "dim2")]
 use godot::classes::physics_server_2d::*;
 #[cfg(feature = "dim3")]
@@ -18,6 +19,8 @@ use spaces::rapier_space_state::RapierSpaceState;
 use super::PhysicsDirectSpaceState;
 use super::RapierDirectSpaceState;
 use crate::bodies::rapier_collision_object::*;
+use crate::joints::rapier_joint::IRapierJoint;
+use crate::joints::rapier_joint::RapierJoint;
 use crate::rapier_wrapper::prelude::*;
 use crate::servers::rapier_project_settings::*;
 use crate::types::*;
@@ -30,6 +33,22 @@ const DEFAULT_GRAVITY_VECTOR: &str = "physics/3d/default_gravity_vector";
 const DEFAULT_GRAVITY: &str = "physics/2d/default_gravity";
 #[cfg(feature = "dim3")]
 const DEFAULT_GRAVITY: &str = "physics/3d/default_gravity";
+#[cfg(feature = "dim2")]
+const SLEEP_THRESHOLD_LINEAR: &str = "physics/2d/sleep_threshold_linear";
+#[cfg(feature = "dim3")]
+const SLEEP_THRESHOLD_LINEAR: &str = "physics/3d/sleep_threshold_linear";
+#[cfg(feature = "dim2")]
+const SLEEP_THRESHOLD_ANGULAR: &str = "physics/2d/sleep_threshold_angular";
+#[cfg(feature = "dim3")]
+const SLEEP_THRESHOLD_ANGULAR: &str = "physics/3d/sleep_threshold_angular";
+#[cfg(feature = "dim2")]
+const TIME_BEFORE_SLEEP: &str = "physics/2d/time_before_sleep";
+#[cfg(feature = "dim3")]
+const TIME_BEFORE_SLEEP: &str = "physics/3d/time_before_sleep";
+#[cfg(feature = "dim2")]
+const DEFAULT_CONTACT_RECYCLE_RADIUS: real = 1.0;
+#[cfg(feature = "dim3")]
+const DEFAULT_CONTACT_RECYCLE_RADIUS: real = 0.01;
 #[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
 pub struct SpaceExport<'a> {
     space: &'a RapierSpaceState,
@@ -42,7 +61,16 @@ pub struct SpaceImport {
 }
 pub struct RapierSpace {
     direct_access: Option<Gd<PhysicsDirectSpaceState>>,
-    contact_max_allowed_penetration: real,
+    contact_recycle_radius: real,
+    contact_max_separation: Option<real>,
+    contact_max_allowed_penetration: Option<real>,
+    contact_default_bias: Option<real>,
+    #[cfg(feature = "dim2")]
+    constraint_default_bias: Option<real>,
+    solver_iterations: Option<usize>,
+    body_linear_velocity_sleep_threshold: real,
+    body_angular_velocity_sleep_threshold: real,
+    body_time_to_sleep: real,
     default_gravity_dir: Vector,
     default_gravity_value: real,
     default_linear_damping: real,
@@ -50,6 +78,9 @@ pub struct RapierSpace {
     contact_debug: PackedVectorArray,
     contact_debug_count: usize,
     ghost_collision_distance: real,
+    broken_joints: Vec<Rid>,
+    joint_broken_callback: Option<Callable>,
+    joints_order_dirty: bool,
     state: RapierSpaceState,
 }
 impl RapierSpace {
@@ -68,9 +99,24 @@ impl RapierSpace {
             .unwrap_or_default();
         let default_gravity_value =
             variant_to_float(&project_settings.get_setting_with_override(DEFAULT_GRAVITY));
+        let body_linear_velocity_sleep_threshold =
+            variant_to_float(&project_settings.get_setting_with_override(SLEEP_THRESHOLD_LINEAR));
+        let body_angular_velocity_sleep_threshold =
+            variant_to_float(&project_settings.get_setting_with_override(SLEEP_THRESHOLD_ANGULAR));
+        let body_time_to_sleep =
+            variant_to_float(&project_settings.get_setting_with_override(TIME_BEFORE_SLEEP));
         let space = Self {
             direct_access: Some(direct_access.upcast()),
-            contact_max_allowed_penetration: 0.0,
+            contact_recycle_radius: DEFAULT_CONTACT_RECYCLE_RADIUS,
+            contact_max_separation: None,
+            contact_max_allowed_penetration: None,
+            contact_default_bias: None,
+            #[cfg(feature = "dim2")]
+            constraint_default_bias: None,
+            solver_iterations: None,
+            body_linear_velocity_sleep_threshold,
+            body_angular_velocity_sleep_threshold,
+            body_time_to_sleep,
             default_gravity_dir,
             default_gravity_value,
             default_linear_damping: 0.0,
@@ -78,6 +124,9 @@ impl RapierSpace {
             contact_debug: PackedVectorArray::new(),
             contact_debug_count: 0,
             ghost_collision_distance: RapierProjectSettings::get_ghost_collision_distance(),
+            broken_joints: Vec::default(),
+            joint_broken_callback: None,
+            joints_order_dirty: false,
             state: RapierSpaceState::new(id, physics_engine, &Self::get_world_settings()),
         };
         physics_spaces.insert(rid, space);
@@ -140,6 +189,11 @@ impl RapierSpace {
                 }
             }
         }
+        if let Some(joint_broken_callback) = &self.joint_broken_callback {
+            for joint in &self.broken_joints {
+                queries.push((joint_broken_callback.clone(), vec![joint.to_variant()]));
+            }
+        }
         for area_handle in self.state.get_monitor_query_list().clone() {
             if let Some(area) = physics_collision_objects.get(&get_id_rid(area_handle, physics_ids))
             {
@@ -180,6 +234,7 @@ impl RapierSpace {
             space
                 .state
                 .set_time_stepped(space.state.get_time_stepped() + step);
+            space.state.set_last_step(step);
             area_update_list = space.get_state().get_area_update_list().clone();
         }
         for area in area_update_list {
@@ -253,7 +308,39 @@ impl RapierSpace {
         settings.pixel_liquid_gravity =
             vector_to_rapier(default_gravity_dir) * default_gravity_value;
         settings.pixel_gravity = vector_to_rapier(default_gravity_dir) * default_gravity_value;
+        for joint in physics_data.joints.values_mut() {
+            if joint.get_base().get_space_id() != space_handle {
+                continue;
+            }
+            match joint {
+                RapierJoint::RapierRopeJoint(joint) => {
+                    joint.update_spring(&mut physics_data.physics_engine)
+                }
+                #[cfg(feature = "dim3")]
+                RapierJoint::RapierConeTwistJoint3D(joint) => {
+                    joint.update_limits(step, &mut physics_data.physics_engine)
+                }
+                #[cfg(feature = "dim3")]
+                RapierJoint::RapierSphericalJoint3D(joint) => {
+                    joint.update_spring(step, &mut physics_data.physics_engine)
+                }
+                #[cfg(feature = "dim3")]
+                RapierJoint::RapierSliderJoint3D(joint) => {
+                    joint.update_motors(step, &mut physics_data.physics_engine)
+                }
+                _ => {}
+            }
+        }
+        #[cfg(feature = "dim3")]
+        Self::update_vehicles(step, space_rid, physics_data);
+        if let Some(space) = physics_data.spaces.get_mut(space_rid)
+            && space.joints_order_dirty
+        {
+            space.joints_order_dirty = false;
+            Self::sort_joints_by_priority(space_handle, physics_data);
+        }
         if let Some(space) = physics_data.spaces.get_mut(space_rid) {
+            space.apply_param_overrides(&mut settings);
             // this calls into rapier
             physics_data.physics_engine.world_step(
                 space_handle,
@@ -270,6 +357,114 @@ impl RapierSpace {
                 &physics_data.ids,
             );
         }
+        let breakable_joints: Vec<(JointHandle, real)> = physics_data
+            .joints
+            .values()
+            .map(|joint| joint.get_base())
+            .filter(|joint| {
+                joint.get_space_id() == space_handle && joint.is_valid() && !joint.is_broken()
+            })
+            .map(|joint| (joint.get_handle(), joint.get_max_force() as real * step))
+            .collect();
+        let broken_handles = physics_data
+            .physics_engine
+            .joints_break(space_handle, &breakable_joints);
+        let mut broken_joints = Vec::default();
+        for joint in physics_data.joints.values_mut() {
+            let joint = joint.get_mut_base();
+            if joint.get_space_id() == space_handle
+                && joint.is_valid()
+                && broken_handles.contains(&joint.get_handle())
+            {
+                joint.set_broken();
+                broken_joints.push(joint.get_rid());
+            }
+        }
+        if let Some(space) = physics_data.spaces.get_mut(space_rid) {
+            space.broken_joints = broken_joints;
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    fn update_vehicles(step: real, space_rid: &Rid, physics_data: &mut PhysicsData) {
+        let Some(space) = physics_data.spaces.get(space_rid) else {
+            return;
+        };
+        let space_handle = space.get_state().get_id();
+        for body in space.get_state().get_vehicle_update_list().clone() {
+            let body_rid = get_id_rid(body, &physics_data.ids);
+            let Some(body) = physics_data
+                .collision_objects
+                .get_mut(&body_rid)
+                .and_then(|body| body.get_mut_body())
+            else {
+                continue;
+            };
+            let Some(mut vehicle) = body.take_vehicle() else {
+                continue;
+            };
+            let handle_excluded_info = QueryExcludedInfo {
+                query_collision_layer_mask: body.get_base().get_collision_mask(),
+                query_canvas_instance_id: 0,
+                query_exclude: Vec::new(),
+                query_exclude_size: 0,
+                query_exclude_body: body_rid.to_u64() as i64,
+            };
+            physics_data.physics_engine.vehicle_update(
+                space_handle,
+                &mut vehicle,
+                step,
+                &handle_excluded_info,
+                &physics_data.collision_objects,
+                &physics_data.ids,
+                space,
+            );
+            if let Some(body) = physics_data
+                .collision_objects
+                .get_mut(&body_rid)
+                .and_then(|body| body.get_mut_body())
+            {
+                body.restore_vehicle(vehicle);
+            }
+        }
+    }
+
+    pub fn mark_joints_order_dirty(&mut self) {
+        self.joints_order_dirty = true;
+    }
+
+    // Joints with a higher solver priority are inserted first, so the solver goes through them first.
+    // This is only a best-effort hint: Godot gives each priority level above 1 its own extra solver passes,
+    // rapier solves every joint the same number of times, so here a higher priority only means an earlier turn.
+    fn sort_joints_by_priority(space_handle: WorldHandle, physics_data: &mut PhysicsData) {
+        let mut joints: Vec<(i32, RapierId, Rid, JointHandle)> = physics_data
+            .joints
+            .values()
+            .map(|joint| joint.get_base())
+            .filter(|joint| {
+                joint.get_space_id() == space_handle
+                    && joint.is_valid()
+                    && !joint.get_handle().multibody
+            })
+            .map(|joint| {
+                (
+                    joint.get_solver_priority(),
+                    joint.get_id(),
+                    joint.get_rid(),
+                    joint.get_handle(),
+                )
+            })
+            .collect();
+        joints.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        let joint_handles: Vec<JointHandle> = joints.iter().map(|joint| joint.3).collect();
+        let new_joint_handles = physics_data
+            .physics_engine
+            .joint_reinsert_in_order(space_handle, &joint_handles);
+        for (joint, handle) in joints.iter().zip(new_joint_handles) {
+            if let Some(joint) = physics_data.joints.get_mut(&joint.2) {
+                joint.get_mut_base().set_handle(handle);
+            }
+        }
     }
 
     pub fn get_last_step() -> real {
@@ -283,6 +478,133 @@ impl RapierSpace {
         last_step
     }
 
+    pub fn set_param(&mut self, param: SpaceParameter, value: real) {
+        match param {
+            // Rapier recomputes its contact manifolds every step and has nothing to recycle,
+            // the value is only stored so it can be read back.
+            SpaceParameter::CONTACT_RECYCLE_RADIUS => self.contact_recycle_radius = value,
+            SpaceParameter::CONTACT_MAX_SEPARATION => {
+                self.contact_max_separation = Some(value.max(0.0))
+            }
+            SpaceParameter::CONTACT_MAX_ALLOWED_PENETRATION => {
+                self.contact_max_allowed_penetration = Some(value.max(0.0))
+            }
+            SpaceParameter::CONTACT_DEFAULT_BIAS => {
+                self.contact_default_bias = Some(value.clamp(0.0, 1.0))
+            }
+            #[cfg(feature = "dim2")]
+            SpaceParameter::CONSTRAINT_DEFAULT_BIAS => {
+                self.constraint_default_bias = Some(value.clamp(0.0, 1.0))
+            }
+            SpaceParameter::BODY_LINEAR_VELOCITY_SLEEP_THRESHOLD => {
+                self.body_linear_velocity_sleep_threshold = value
+            }
+            SpaceParameter::BODY_ANGULAR_VELOCITY_SLEEP_THRESHOLD => {
+                self.body_angular_velocity_sleep_threshold = value
+            }
+            SpaceParameter::BODY_TIME_TO_SLEEP => self.body_time_to_sleep = value,
+            SpaceParameter::SOLVER_ITERATIONS => {
+                self.solver_iterations = Some(value.max(1.0) as usize)
+            }
+            _ => {}
+        }
+    }
+
+    pub fn get_param(&self, param: SpaceParameter) -> real {
+        let length_unit = RapierProjectSettings::get_length_unit();
+        match param {
+            SpaceParameter::CONTACT_RECYCLE_RADIUS => self.contact_recycle_radius,
+            SpaceParameter::CONTACT_MAX_SEPARATION => self.contact_max_separation.unwrap_or(
+                RapierProjectSettings::get_normalized_prediction_distance() * length_unit,
+            ),
+            SpaceParameter::CONTACT_MAX_ALLOWED_PENETRATION => {
+                self.contact_max_allowed_penetration.unwrap_or(
+                    RapierProjectSettings::get_normalized_allowed_linear_error() * length_unit,
+                )
+            }
+            SpaceParameter::CONTACT_DEFAULT_BIAS => {
+                self.contact_default_bias.unwrap_or_else(|| {
+                    natural_frequency_to_bias(
+                        RapierProjectSettings::get_contact_natural_frequency(),
+                        RapierProjectSettings::get_contact_damping_ratio(),
+                        Self::get_last_step(),
+                    )
+                })
+            }
+            #[cfg(feature = "dim2")]
+            SpaceParameter::CONSTRAINT_DEFAULT_BIAS => {
+                self.constraint_default_bias.unwrap_or_else(|| {
+                    natural_frequency_to_bias(
+                        RapierProjectSettings::get_joint_natural_frequency(),
+                        RapierProjectSettings::get_joint_damping_ratio(),
+                        Self::get_last_step(),
+                    )
+                })
+            }
+            SpaceParameter::BODY_LINEAR_VELOCITY_SLEEP_THRESHOLD => {
+                self.body_linear_velocity_sleep_threshold
+            }
+            SpaceParameter::BODY_ANGULAR_VELOCITY_SLEEP_THRESHOLD => {
+                self.body_angular_velocity_sleep_threshold
+            }
+            SpaceParameter::BODY_TIME_TO_SLEEP => self.body_time_to_sleep,
+            SpaceParameter::SOLVER_ITERATIONS => self
+                .solver_iterations
+                .unwrap_or(RapierProjectSettings::get_solver_num_solver_iterations() as usize)
+                as real,
+            _ => 0.0,
+        }
+    }
+
+    // Only the parameters that were explicitly set on this space override the project settings.
+    fn apply_param_overrides(&self, settings: &mut SimulationSettings) {
+        if let Some(contact_max_separation) = self.contact_max_separation {
+            settings.normalized_prediction_distance = contact_max_separation / settings.length_unit;
+        }
+        if let Some(contact_max_allowed_penetration) = self.contact_max_allowed_penetration {
+            settings.normalized_allowed_linear_error =
+                contact_max_allowed_penetration / settings.length_unit;
+        }
+        if let Some(contact_default_bias) = self.contact_default_bias {
+            settings.contact_natural_frequency = bias_to_natural_frequency(
+                contact_default_bias,
+                settings.contact_damping_ratio,
+                settings.dt,
+            );
+        }
+        #[cfg(feature = "dim2")]
+        if let Some(constraint_default_bias) = self.constraint_default_bias {
+            settings.joint_natural_frequency = bias_to_natural_frequency(
+                constraint_default_bias,
+                settings.joint_damping_ratio,
+                settings.dt,
+            );
+        }
+        if let Some(solver_iterations) = self.solver_iterations {
+            settings.num_solver_iterations = solver_iterations;
+        }
+    }
+
+    pub fn get_body_activation(&self) -> (real, real, real) {
+        (
+            self.body_angular_velocity_sleep_threshold,
+            self.body_linear_velocity_sleep_threshold,
+            self.body_time_to_sleep,
+        )
+    }
+
+    pub fn get_broken_joints(&self) -> &Vec<Rid> {
+        &self.broken_joints
+    }
+
+    pub fn set_joint_broken_callback(&mut self, callback: Callable) {
+        if callback.is_valid() {
+            self.joint_broken_callback = Some(callback);
+        } else {
+            self.joint_broken_callback = None;
+        }
+    }
+
     pub fn set_default_area_param(&mut self, param: AreaParameter, value: Variant) {
         match param {
             AreaParameter::GRAVITY => self.default_gravity_value = variant_to_float(&value),
@@ -357,7 +679,7 @@ impl RapierSpace {
     }
 
     pub fn get_contact_max_allowed_penetration(&self) -> real {
-        self.contact_max_allowed_penetration
+        self.contact_max_allowed_penetration.unwrap_or_default()
     }
 
     pub fn get_world_settings() -> WorldSettings {
@@ -447,3 +769,14 @@ impl RapierSpace {
         self.ghost_collision_distance
     }
 }
+// Godot bias is the error reduction parameter applied each step, while Rapier derives it from
+// a natural frequency and a damping ratio: erp = dt * w / (dt * w + 2 * damping_ratio).
+fn bias_to_natural_frequency(bias: real, damping_ratio: real, dt: real) -> real {
+    let bias = bias.min(0.999);
+    let angular_frequency = 2.0 * damping_ratio * bias / (dt * (1.0 - bias));
+    angular_frequency / (2.0 * real_consts::PI)
+}
+fn natural_frequency_to_bias(natural_frequency: real, damping_ratio: real, dt: real) -> real {
+    let angular_frequency = natural_frequency * 2.0 * real_consts::PI;
+    dt * angular_frequency / (dt * angular_frequency + 2.0 * damping_ratio)
+}
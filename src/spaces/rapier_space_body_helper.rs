@@ -1,6 +1,7 @@
 use std::ops::Deref;
 
 use bodies::rapier_collision_object_base::RapierCollisionObjectBase;
+use godot::builtin::real_consts;
 use godot::classes::native::ObjectId;
 use godot::classes::physics_server_2d::BodyMode;
 use godot::prelude::*;
@@ -10,6 +11,7 @@ use rapier::math::DEFAULT_EPSILON;
 use servers::rapier_physics_singleton::PhysicsCollisionObjects;
 use servers::rapier_physics_singleton::PhysicsIds;
 use servers::rapier_physics_singleton::PhysicsShapes;
+use servers::rapier_project_settings::RapierProjectSettings;
 use shapes::rapier_shape::IRapierShape;
 use shapes::rapier_shape::RapierShape;
 
@@ -217,11 +219,22 @@ impl RapierSpace {
         let mut recovered = false;
         let mut recover_attempts = BODY_MOTION_RECOVER_ATTEMPTS;
         let body_aabb = p_body.get_aabb(physics_shapes, physics_ids);
+        // Shape margins are part of the contact prediction, so look for colliders within them too.
+        let mut shapes_margin: real = 0.0;
+        for body_shape_idx in 0..shape_count {
+            if let Some(body_shape) = physics_shapes.get(
+                &p_body
+                    .get_base()
+                    .get_shape(physics_ids, body_shape_idx as usize),
+            ) {
+                shapes_margin = shapes_margin.max(body_shape.get_base().get_margin());
+            }
+        }
         loop {
             let mut results = [PointHitInfo::default(); 32];
             // Undo the currently transform the physics server is aware of and apply the provided one
             let margin_aabb = *p_transform * body_aabb;
-            let margin_aabb = margin_aabb.grow(p_margin);
+            let margin_aabb = margin_aabb.grow(p_margin + shapes_margin);
             let result_count = self.rapier_intersect_aabb(
                 margin_aabb,
                 p_body.get_base().get_collision_mask(),
@@ -249,10 +262,11 @@ impl RapierSpace {
                 {
                     let body_shape_transform =
                         *p_transform * p_body.get_base().get_shape_transform(body_shape_idx);
-                    let body_shape_info = shape_info_from_body_shape(
+                    let mut body_shape_info = shape_info_from_body_shape(
                         body_shape.get_base().get_id(),
                         body_shape_transform,
                     );
+                    body_shape_info.pixel_margin = body_shape.get_base().get_margin();
                     for result_idx in 0..result_count {
                         let result_idx = result_idx as usize;
                         let result = &mut results[result_idx];
@@ -281,11 +295,15 @@ impl RapierSpace {
                                             * collision_body
                                                 .get_base()
                                                 .get_shape_transform(shape_index);
-                                    let col_shape_info = shape_info_from_body_shape(
+                                    let mut col_shape_info = shape_info_from_body_shape(
                                         col_shape.get_base().get_id(),
                                         col_shape_transform,
                                     );
-                                    let contact = physics_engine.shapes_contact(
+                                    col_shape_info.pixel_margin = col_shape.get_base().get_margin();
+                                    let contact = body_shape_contact(
+                                        physics_engine,
+                                        body_shape,
+                                        &body_shape_transform,
                                         body_shape_info,
                                         col_shape_info,
                                         p_margin,
@@ -318,7 +336,8 @@ impl RapierSpace {
                                         // Only recover if there is penetration.
                                         recover_step -= n
                                             * (depth - min_contact_depth)
-                                            * BODY_MOTION_RECOVER_RATIO;
+                                            * BODY_MOTION_RECOVER_RATIO
+                                            * collision_priority_weight(p_body, collision_body);
                                     }
                                 }
                             }
@@ -348,7 +367,7 @@ impl RapierSpace {
         p_body: &RapierBody,
         p_transform: &Transform,
         p_motion: Vector,
-        _p_collide_separation_ray: bool,
+        p_collide_separation_ray: bool,
         _contact_max_allowed_penetration: f32,
         p_margin: f32,
         p_closest_safe: &mut f32,
@@ -395,18 +414,17 @@ impl RapierSpace {
                     body_shape.get_base().get_id(),
                     body_shape_transform,
                 );
+                body_shape_info.pixel_margin = body_shape.get_base().get_margin();
                 // Colliding separation rays allows to properly snap to the ground,
                 // otherwise it's not needed in regular motion.
-                //if !p_collide_separation_ray
-                //    && body_shape.get_type() == PhysicsServer2D::SHAPE_SEPARATION_RAY
-                //{
                 // When slide on slope is on, separation ray shape acts like a
                 // regular shape.
-                //if !body_shape.downcast_ref::<RapierSeparationRayShape>().unwrap().get_slide_on_slope()
-                //{
-                //    continue;
-                //}
-                //}
+                if !p_collide_separation_ray
+                    && let RapierShape::RapierSeparationRayShape(separation_ray) = body_shape
+                    && !separation_ray.get_slide_on_slope()
+                {
+                    continue;
+                }
                 let mut best_safe = 1.0;
                 let mut best_unsafe = 1.0;
                 let _stuck = false;
@@ -434,10 +452,11 @@ impl RapierSpace {
                             ) {
                                 let col_shape_transform = collision_body.get_base().get_transform()
                                     * collision_body.get_base().get_shape_transform(shape_index);
-                                let col_shape_info = shape_info_from_body_shape(
+                                let mut col_shape_info = shape_info_from_body_shape(
                                     col_shape.get_base().get_id(),
                                     col_shape_transform,
                                 );
+                                col_shape_info.pixel_margin = col_shape.get_base().get_margin();
                                 // stuck logic, check if body collides in place
                                 body_shape_info.transform.translation.vector =
                                     vector_to_rapier(body_shape_transform.origin);
@@ -612,10 +631,11 @@ impl RapierSpace {
                     .get_base()
                     .get_shape_transform(body_shape_idx as usize);
             if let Some(body_shape_obj) = physics_shapes.get(&body_shape) {
-                let body_shape_info = shape_info_from_body_shape(
+                let mut body_shape_info = shape_info_from_body_shape(
                     body_shape_obj.get_base().get_id(),
                     body_shape_transform,
                 );
+                body_shape_info.pixel_margin = body_shape_obj.get_base().get_margin();
                 for result_idx in 0..result_count {
                     let result = &mut results[result_idx as usize];
                     if !result.user_data.is_valid() {
@@ -638,11 +658,15 @@ impl RapierSpace {
                             if let Some(col_shape) = physics_shapes.get(&col_shape_rid) {
                                 let col_shape_transform = collision_body.get_base().get_transform()
                                     * collision_body.get_base().get_shape_transform(shape_index);
-                                let col_shape_info = shape_info_from_body_shape(
+                                let mut col_shape_info = shape_info_from_body_shape(
                                     col_shape.get_base().get_id(),
                                     col_shape_transform,
                                 );
-                                let contact = physics_engine.shapes_contact(
+                                col_shape_info.pixel_margin = col_shape.get_base().get_margin();
+                                let contact = body_shape_contact(
+                                    physics_engine,
+                                    body_shape_obj,
+                                    &body_shape_transform,
                                     body_shape_info,
                                     col_shape_info,
                                     p_margin,
@@ -701,6 +725,141 @@ impl RapierSpace {
         }
         false
     }
+
+    // Runs the kinematic character controller with the first enabled shape of the body.
+    // The body itself is not moved, the caller applies the returned translation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn character_move(
+        &self,
+        body: &RapierBody,
+        desired_translation: Vector,
+        options: &Dictionary,
+        physics_engine: &mut PhysicsEngine,
+        physics_shapes: &PhysicsShapes,
+        physics_ids: &PhysicsIds,
+        physics_collision_objects: &PhysicsCollisionObjects,
+    ) -> Dictionary {
+        let mut result = Dictionary::new();
+        result.set("translation", Vector::default());
+        result.set("grounded", false);
+        result.set("is_sliding_down_slope", false);
+        result.set("collisions", VariantArray::new());
+        let body_base = body.get_base();
+        let mut shape_infos = Vec::new();
+        for shape_idx in 0..body_base.get_shape_count() as usize {
+            if body_base.is_shape_disabled(shape_idx) {
+                continue;
+            }
+            if let Some(body_shape) =
+                physics_shapes.get(&body_base.get_shape(physics_ids, shape_idx))
+            {
+                let shape_transform =
+                    body_base.get_transform() * body_base.get_shape_transform(shape_idx);
+                shape_infos.push(shape_info_from_body_shape(
+                    body_shape.get_base().get_id(),
+                    shape_transform,
+                ));
+            }
+        }
+        if shape_infos.is_empty() {
+            return result;
+        }
+        let length_unit = RapierProjectSettings::get_length_unit();
+        let get_float = |key: &str, default: real| {
+            options
+                .get(key)
+                .map_or(default, |value| variant_to_float(&value))
+        };
+        let get_optional_float = |key: &str| {
+            options
+                .get(key)
+                .and_then(|value| variant_to_optional_float(&value))
+        };
+        let get_bool = |key: &str, default: bool| {
+            options
+                .get(key)
+                .and_then(|value| value.try_to::<bool>().ok())
+                .unwrap_or(default)
+        };
+        let up = options
+            .get("up")
+            .and_then(|value| value.try_to::<Vector>().ok())
+            .filter(|up| *up != Vector::default())
+            .unwrap_or(Vector::UP);
+        let move_options = CharacterMoveOptions {
+            up: vector_to_rapier(up.normalized()),
+            pixel_offset: get_float("offset", 0.01 * length_unit),
+            slide: get_bool("slide", true),
+            pixel_autostep_max_height: get_optional_float("autostep_max_height"),
+            pixel_autostep_min_width: get_float("autostep_min_width", 0.0),
+            autostep_include_dynamic_bodies: get_bool("autostep_include_dynamic_bodies", false),
+            max_slope_climb_angle: get_float("max_slope_climb_angle", real_consts::FRAC_PI_4),
+            min_slope_slide_angle: get_float("min_slope_slide_angle", real_consts::FRAC_PI_4),
+            pixel_snap_to_ground: get_optional_float("snap_to_ground"),
+            normal_nudge_factor: get_float("normal_nudge_factor", 1.0e-4),
+            apply_impulses: get_bool("apply_impulses", false),
+            character_mass: get_float("mass", body.get_mass()),
+        };
+        // The step of the last simulation, the project tick rate is only used before the space stepped.
+        let mut default_step = self.get_state().get_last_step();
+        if default_step <= 0.0 {
+            default_step = RapierSpace::get_last_step();
+        }
+        let step = get_float("delta", default_step);
+        let handle_excluded_info = QueryExcludedInfo {
+            query_collision_layer_mask: body_base.get_collision_mask(),
+            query_canvas_instance_id: 0,
+            query_exclude: Vec::new(),
+            query_exclude_size: 0,
+            query_exclude_body: body_base.get_rid().to_u64() as i64,
+        };
+        let move_result = physics_engine.character_move(
+            self.get_state().get_id(),
+            body_base.get_body_handle(),
+            &shape_infos,
+            vector_to_rapier(desired_translation),
+            &move_options,
+            step,
+            &handle_excluded_info,
+            physics_collision_objects,
+            physics_ids,
+            self,
+        );
+        let mut collisions = VariantArray::new();
+        for collision in &move_result.collisions {
+            let (collider, collider_shape) = RapierCollisionObjectBase::get_collider_user_data(
+                &collision.user_data,
+                physics_ids,
+            );
+            let mut collider_id = 0;
+            if let Some(collision_object) = physics_collision_objects.get(&collider) {
+                collider_id = collision_object.get_base().get_instance_id();
+            }
+            let mut collision_dictionary = Dictionary::new();
+            collision_dictionary.set("collider", collider);
+            collision_dictionary.set("collider_id", collider_id as i64);
+            collision_dictionary.set("collider_shape", collider_shape as i32);
+            collision_dictionary.set("position", vector_to_godot(collision.pixel_position));
+            collision_dictionary.set("normal", vector_to_godot(collision.normal));
+            collision_dictionary.set(
+                "translation_applied",
+                vector_to_godot(collision.pixel_translation_applied),
+            );
+            collision_dictionary.set(
+                "translation_remaining",
+                vector_to_godot(collision.pixel_translation_remaining),
+            );
+            collisions.push(&collision_dictionary.to_variant());
+        }
+        result.set(
+            "translation",
+            vector_to_godot(move_result.pixel_translation),
+        );
+        result.set("grounded", move_result.grounded);
+        result.set("is_sliding_down_slope", move_result.is_sliding_down_slope);
+        result.set("collisions", collisions);
+        result
+    }
 }
 #[cfg(feature = "dim2")]
 #[allow(clippy::too_many_arguments)]
@@ -749,6 +908,37 @@ fn set_collision_info(
     });
     p_result.collision_count = 1;
 }
+// Separation rays go from the shape origin along the local Y axis in 2D and the local Z axis in 3D.
+#[cfg(feature = "dim2")]
+fn get_separation_ray_end(transform: &Transform2D, length: real) -> Vector {
+    transform.origin + transform.b * length
+}
+#[cfg(feature = "dim3")]
+fn get_separation_ray_end(transform: &Transform3D, length: real) -> Vector {
+    transform.origin + transform.basis.col_c().normalized() * length
+}
+// Separation rays only separate along their direction, other shapes use a regular contact.
+fn body_shape_contact(
+    physics_engine: &PhysicsEngine,
+    body_shape: &RapierShape,
+    body_shape_transform: &Transform,
+    body_shape_info: ShapeInfo,
+    col_shape_info: ShapeInfo,
+    margin: real,
+) -> ContactResult {
+    if let RapierShape::RapierSeparationRayShape(separation_ray) = body_shape {
+        return physics_engine.separation_ray_contact(
+            vector_to_rapier(body_shape_transform.origin),
+            vector_to_rapier(get_separation_ray_end(
+                body_shape_transform,
+                separation_ray.get_length() + margin,
+            )),
+            col_shape_info,
+            separation_ray.get_slide_on_slope(),
+        );
+    }
+    physics_engine.shapes_contact(body_shape_info, col_shape_info, margin)
+}
 #[cfg(feature = "dim2")]
 fn get_transform_forward(transform: &Transform2D) -> Vector {
     -Vector2::new(transform.a.y, transform.b.y)
@@ -810,3 +1000,13 @@ impl PhysicsEngine {
         false
     }
 }
+// Bodies with a lower collision priority than the moving body push it out less.
+fn collision_priority_weight(body: &RapierBody, collision_body: &RapierBody) -> real {
+    if collision_body.get_base().mode.ord() < BodyMode::RIGID.ord() {
+        return 1.0;
+    }
+    real::min(
+        collision_body.get_collision_priority() / body.get_collision_priority(),
+        1.0,
+    )
+}
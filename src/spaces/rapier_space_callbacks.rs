@@ -109,6 +109,7 @@ impl RapierSpace {
                 if let Some(body1) = collision_object_1.get_body() {
                     result.previous_linear_velocity1 =
                         vector_to_rapier(body1.get_previous_linear_velocity());
+                    result.collision_priority1 = body1.get_collision_priority();
                 }
                 result.body2 = collision_base_2.is_shape_set_as_one_way_collision(shape2);
                 result.pixel_body2_margin =
@@ -116,6 +117,7 @@ impl RapierSpace {
                 if let Some(body2) = collision_object_2.get_body() {
                     result.previous_linear_velocity2 =
                         vector_to_rapier(body2.get_previous_linear_velocity());
+                    result.collision_priority2 = body2.get_collision_priority();
                 }
             }
         }
@@ -360,6 +362,8 @@ impl RapierSpace {
             let normal = contact_info.normal;
             // send just impulse directly along normal
             let impulse = contact_info.pixel_impulse * vector_to_godot(normal);
+            // friction impulse magnitude, its direction is chosen by the solver
+            let tangent_impulse = contact_info.pixel_tangent_impulse.norm();
             let vel_pos1 = contact_info.pixel_velocity_pos_1;
             let vel_pos2 = contact_info.pixel_velocity_pos_2;
             if let Some(body1) = p_object1.get_mut_body() {
@@ -394,6 +398,10 @@ impl RapierSpace {
                             body2.get_base().get_id(),
                             vector_to_godot(vel_pos2),
                             impulse,
+                            tangent_impulse,
+                            contact_info.feature_id_1,
+                            contact_info.feature_id_2,
+                            contact_info.friction,
                         );
                     }
                     if body2.can_report_contacts() {
@@ -410,6 +418,10 @@ impl RapierSpace {
                             body1.get_base().get_id(),
                             vector_to_godot(vel_pos1),
                             -impulse,
+                            tangent_impulse,
+                            contact_info.feature_id_2,
+                            contact_info.feature_id_1,
+                            contact_info.friction,
                         );
                     }
                 }
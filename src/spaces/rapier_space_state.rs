@@ -51,12 +51,14 @@ pub struct RapierSpaceState {
     active_list: HashSet<RapierId>,
     mass_properties_update_list: HashSet<RapierId>,
     gravity_update_list: HashSet<RapierId>,
+    vehicle_update_list: HashSet<RapierId>,
     state_query_list: HashSet<RapierId>,
     force_integrate_query_list: HashSet<RapierId>,
     monitor_query_list: HashSet<RapierId>,
     area_update_list: HashSet<RapierId>,
     body_area_update_list: HashSet<RapierId>,
     time_stepped: f32,
+    last_step: f32,
     active_objects: i32,
     id: WorldHandle,
 }
@@ -89,6 +91,14 @@ impl RapierSpaceState {
         self.gravity_update_list.remove(&body);
     }
 
+    pub fn body_add_to_vehicle_update_list(&mut self, body: RapierId) {
+        self.vehicle_update_list.insert(body);
+    }
+
+    pub fn body_remove_from_vehicle_update_list(&mut self, body: RapierId) {
+        self.vehicle_update_list.remove(&body);
+    }
+
     pub fn body_add_to_active_list(&mut self, body: RapierId) {
         self.active_list.insert(body);
     }
@@ -174,6 +184,14 @@ impl RapierSpaceState {
         self.time_stepped = time;
     }
 
+    pub fn get_last_step(&self) -> f32 {
+        self.last_step
+    }
+
+    pub fn set_last_step(&mut self, step: f32) {
+        self.last_step = step;
+    }
+
     pub fn get_active_list(&self) -> &HashSet<RapierId> {
         &self.active_list
     }
@@ -194,6 +212,10 @@ impl RapierSpaceState {
         &self.gravity_update_list
     }
 
+    pub fn get_vehicle_update_list(&self) -> &HashSet<RapierId> {
+        &self.vehicle_update_list
+    }
+
     pub fn get_active_bodies(&self) -> Vec<RapierId> {
         self.active_list.clone().into_iter().collect()
     }
@@ -254,6 +276,7 @@ mod tests {
         assert!(state.get_active_list().is_empty());
         assert!(state.get_mass_properties_update_list().is_empty());
         assert!(state.get_gravity_update_list().is_empty());
+        assert!(state.get_vehicle_update_list().is_empty());
         assert!(state.get_state_query_list().is_empty());
         assert!(state.get_force_integrate_query_list().is_empty());
         assert!(state.get_monitor_query_list().is_empty());
@@ -298,6 +321,16 @@ mod tests {
         assert!(!state.get_gravity_update_list().contains(&rb_id));
     }
     #[test]
+    fn test_body_add_and_remove_from_vehicle_update_list() {
+        let mut physics_engine = PhysicsEngine::default();
+        let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
+        let rb_id = 0;
+        state.body_add_to_vehicle_update_list(rb_id);
+        assert!(state.get_vehicle_update_list().contains(&rb_id));
+        state.body_remove_from_vehicle_update_list(rb_id);
+        assert!(!state.get_vehicle_update_list().contains(&rb_id));
+    }
+    #[test]
     fn test_body_add_and_remove_from_state_query_list() {
         let mut physics_engine = PhysicsEngine::default();
         let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
@@ -388,6 +421,14 @@ mod tests {
         assert_eq!(state.get_time_stepped(), 0.0016);
     }
     #[test]
+    fn test_set_and_get_last_step() {
+        let mut physics_engine = PhysicsEngine::default();
+        let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
+        assert_eq!(state.get_last_step(), 0.0);
+        state.set_last_step(1.0 / 60.0);
+        assert_eq!(state.get_last_step(), 1.0 / 60.0);
+    }
+    #[test]
     fn test_destroy() {
         let mut physics_engine = PhysicsEngine::default();
         let mut state = RapierSpaceState::new(0, &mut physics_engine, &create_world_settings());
@@ -148,3 +148,15 @@ pub fn variant_to_float(variant: &Variant) -> real {
         _ => 0.0,
     }
 }
+pub fn variant_to_optional_float(variant: &Variant) -> Option<real> {
+    match variant.get_type() {
+        VariantType::FLOAT | VariantType::INT => Some(variant_to_float(variant)),
+        _ => None,
+    }
+}
+pub fn optional_float_to_variant(value: Option<real>) -> Variant {
+    match value {
+        Some(value) => value.to_variant(),
+        None => Variant::nil(),
+    }
+}